@@ -0,0 +1,762 @@
+use std::{ffi::CString, fs, io, path::Path, ptr};
+
+use anyhow::{self, Context};
+use chrono::Local;
+use ffmpeg::{codec, format, media::Type, util::frame, Packet, Rational};
+use ffmpeg_next::{self as ffmpeg};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::{
+    decoder::{self, DecodeOptions},
+    input,
+    timeline::Timeline,
+};
+
+/// Container of clips cut from URLs, image sequences and files whose
+/// container FFmpeg can not write.
+const DEFAULT_CLIP_EXTENSION: &str = "mkv";
+
+/// Settings of a clip export.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClipOptions {
+    /// Re-encodes the GOPs at the edges so the clip starts and ends at the
+    /// requested times, instead of cutting on the surrounding keyframes.
+    pub precise: bool,
+    /// Video track the cut follows, every stream is copied into the clip.
+    pub decode: DecodeOptions,
+}
+
+#[derive(Debug)]
+pub struct ClipResult {
+    pub clip_path: String,
+    pub manifest_path: String,
+    pub sha256: String,
+    /// Start of the clip in seconds from the first frame of the source,
+    /// earlier than requested when cut on keyframes.
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Records where a clip comes from and how it was cut.
+#[derive(Debug, Serialize)]
+struct ClipManifest<'a> {
+    source: String,
+    clip: &'a str,
+    sha256: &'a str,
+    size: u64,
+    /// Extension of the source file, unset for URLs and image sequences.
+    source_container: Option<String>,
+    /// Extension of the clip, which differs from the source one when its
+    /// container can not be written.
+    container: &'a str,
+    precise: bool,
+    requested_start: f64,
+    requested_end: f64,
+    start: f64,
+    end: f64,
+    reencoded_gops: usize,
+    created: String,
+}
+
+/// Frames from a keyframe up to the next one, in seconds of the source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Gop {
+    start: f64,
+    /// Next keyframe, unset for the last GOP of the file.
+    end: Option<f64>,
+    /// Copied as is, otherwise decoded and re-encoded.
+    copy: bool,
+}
+
+#[derive(Debug)]
+struct ClipPlan {
+    video_index: usize,
+    seek_position: Option<i64>,
+    /// Keyframes read after seeking before the first GOP of the clip.
+    skipped_gops: usize,
+    gops: Vec<Gop>,
+    /// Range of the clip in seconds of the source.
+    start: f64,
+    end: f64,
+}
+
+impl ClipPlan {
+    fn reencoded_gops(&self) -> usize {
+        self.gops.iter().filter(|gop| !gop.copy).count()
+    }
+}
+
+/// Copies the segment between `time_start` and `time_end` (seconds from the
+/// first frame) into a clip with the codecs and metadata of the source, and
+/// writes its SHA-256 and a manifest next to it.
+pub fn export_clip<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    time_start: f64,
+    time_end: f64,
+    options: ClipOptions,
+) -> anyhow::Result<ClipResult> {
+    if time_start >= time_end {
+        anyhow::bail!("the clip must end after it starts");
+    }
+
+    let (video_name, save_path) = decoder::output_dir(&video_path, save_path.as_ref(), None)?;
+
+    let decode = &options.decode;
+    let mut input_format_context = input::open_input(&video_path, &decode.input)?;
+    let (video_index, video_start) = {
+        let stream = decoder::select_video_stream(&input_format_context, decode.video_track)?;
+        (
+            stream.index(),
+            Timeline::new(&stream, &decode.input).start(),
+        )
+    };
+
+    let plan = plan_clip(
+        &mut input_format_context,
+        video_index,
+        video_start + time_start,
+        video_start + time_end,
+        options.precise,
+    )?;
+
+    let source_container = source_extension(&video_path);
+    let extension = clip_extension(source_container.as_deref());
+    let clip_name = format!(
+        "{}_clip_{}s-{}s.{}",
+        video_name, time_start, time_end, extension
+    );
+    let clip_path = save_path.join(&clip_name);
+
+    let clip_duration = write_clip(&mut input_format_context, &clip_path, &plan, decode)?;
+
+    let sha256 = sha256_file(&clip_path)?;
+    let hash_path = save_path.join(format!("{}.sha256", clip_name));
+    fs::write(&hash_path, format!("{}  {}\n", sha256, clip_name))
+        .with_context(|| format!("failed to save hash {}", hash_path.display()))?;
+
+    let start = plan.start - video_start;
+    let end = start + clip_duration;
+    let manifest = ClipManifest {
+        source: video_path.as_ref().display().to_string(),
+        clip: &clip_name,
+        sha256: &sha256,
+        size: fs::metadata(&clip_path).map_or(0, |metadata| metadata.len()),
+        source_container,
+        container: &extension,
+        precise: options.precise,
+        requested_start: time_start,
+        requested_end: time_end,
+        start,
+        end,
+        reencoded_gops: plan.reencoded_gops(),
+        created: Local::now().to_rfc3339(),
+    };
+    let manifest_path = clip_path.with_extension("json");
+    let json = serde_json::to_string_pretty(&manifest).context("failed to serialize manifest")?;
+    fs::write(&manifest_path, json)
+        .with_context(|| format!("failed to save manifest {}", manifest_path.display()))?;
+
+    Ok(ClipResult {
+        clip_path: clip_path.to_string_lossy().to_string(),
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+        sha256,
+        start,
+        end,
+    })
+}
+
+/// Extension of a source file, for the clip to keep its container.
+fn source_extension<P: AsRef<Path>>(video_path: P) -> Option<String> {
+    let video_path = video_path.as_ref();
+    if input::is_url(video_path) || !video_path.is_file() {
+        return None;
+    }
+    video_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+/// Keeps the container of the source when FFmpeg has a muxer for it. The
+/// re-encoded edges of a precise clip carry their parameter sets in band, which
+/// every container takes.
+fn clip_extension(source_extension: Option<&str>) -> String {
+    let has_muxer = |extension: &str| {
+        let Ok(file_name) = CString::new(format!("clip.{}", extension)) else {
+            return false;
+        };
+        unsafe {
+            !ffmpeg::ffi::av_guess_format(ptr::null(), file_name.as_ptr(), ptr::null()).is_null()
+        }
+    };
+    match source_extension {
+        Some(extension) if has_muxer(extension) => extension.to_owned(),
+        _ => DEFAULT_CLIP_EXTENSION.to_owned(),
+    }
+}
+
+fn keyframe_time(packet: &Packet, time_base: f64) -> Option<f64> {
+    if !packet.is_key() {
+        return None;
+    }
+    packet
+        .pts()
+        .or(packet.dts())
+        .map(|ts| ts as f64 * time_base)
+}
+
+/// Lists the keyframes around the range without decoding, to find which GOPs
+/// go into the clip.
+fn plan_clip(
+    input_format_context: &mut format::context::Input,
+    video_index: usize,
+    start: f64,
+    end: f64,
+    precise: bool,
+) -> anyhow::Result<ClipPlan> {
+    let time_base = f64::from(
+        input_format_context
+            .stream(video_index)
+            .context("video stream not found")?
+            .time_base(),
+    );
+
+    // files that can not be seeked are read from the start
+    let seek_position =
+        (start > 0.0).then(|| (start * f64::from(ffmpeg::ffi::AV_TIME_BASE)) as i64);
+    if let Some(position) = seek_position {
+        let _ = input_format_context.seek(position, ..position);
+    }
+
+    let mut keyframes = Vec::new();
+    for (stream, packet) in input_format_context.packets() {
+        if stream.index() != video_index {
+            continue;
+        }
+        if let Some(time) = keyframe_time(&packet, time_base) {
+            keyframes.push(time);
+            if time >= end {
+                break;
+            }
+        }
+    }
+
+    let skipped_gops = keyframes
+        .iter()
+        .rposition(|&time| time <= start)
+        .unwrap_or(0);
+    let gops = select_gops(&keyframes[skipped_gops..], start, end, precise);
+    let (Some(first), Some(last)) = (gops.first(), gops.last()) else {
+        anyhow::bail!("no video frames between the start and the end of the clip");
+    };
+
+    let (clip_start, clip_end) = if precise {
+        (start.max(first.start), end)
+    } else {
+        (first.start, last.end.unwrap_or(f64::INFINITY))
+    };
+
+    Ok(ClipPlan {
+        video_index,
+        seek_position,
+        skipped_gops,
+        start: clip_start,
+        end: clip_end,
+        gops,
+    })
+}
+
+/// GOPs of `keyframes` that overlap `start..end`. In precise mode, the ones
+/// not entirely inside the range are re-encoded.
+fn select_gops(keyframes: &[f64], start: f64, end: f64, precise: bool) -> Vec<Gop> {
+    keyframes
+        .iter()
+        .enumerate()
+        .map(|(i, &gop_start)| {
+            let gop_end = keyframes.get(i + 1).copied();
+            let is_inside = gop_start >= start && gop_end.is_some_and(|gop_end| gop_end <= end);
+            Gop {
+                start: gop_start,
+                end: gop_end,
+                copy: !precise || is_inside,
+            }
+        })
+        .filter(|gop| gop.start < end && gop.end.map_or(true, |gop_end| gop_end > start))
+        .collect()
+}
+
+/// Writes the planned GOPs and the other streams of the range into the clip,
+/// returning its duration in seconds.
+fn write_clip(
+    input_format_context: &mut format::context::Input,
+    clip_path: &Path,
+    plan: &ClipPlan,
+    options: &DecodeOptions,
+) -> anyhow::Result<f64> {
+    let mut output_format_context = format::output(&clip_path)
+        .with_context(|| format!("failed to create clip {}", clip_path.display()))?;
+
+    let mut edge = None;
+    let mut filter = None;
+    let mut stream_mapping = vec![None; input_format_context.nb_streams() as usize];
+    // sparse streams, such as subtitles, do not hold the copy back
+    let mut finished = vec![true; stream_mapping.len()];
+    let mut time_bases = Vec::with_capacity(stream_mapping.len());
+
+    for (index, stream) in input_format_context.streams().enumerate() {
+        time_bases.push(stream.time_base());
+        let medium = stream.parameters().medium();
+        if !matches!(medium, Type::Video | Type::Audio | Type::Subtitle) {
+            continue;
+        }
+
+        let mut parameters = stream.parameters();
+        if index == plan.video_index && plan.reencoded_gops() > 0 {
+            edge = Some(EdgeEncoder::new(&stream, options.threads)?);
+            // the copied packets carry their parameter sets in band, like
+            // the re-encoded ones
+            let name = match parameters.id() {
+                codec::Id::H264 => Some("h264_mp4toannexb"),
+                codec::Id::HEVC => Some("hevc_mp4toannexb"),
+                _ => None,
+            };
+            if let Some(name) = name {
+                let bsf = BitstreamFilter::new(name, &parameters, stream.time_base())?;
+                parameters = bsf.parameters()?;
+                filter = Some(bsf);
+            }
+        }
+
+        let mut output_stream = output_format_context
+            .add_stream(ffmpeg::encoder::find(codec::Id::None))
+            .context("failed to add a stream to the clip")?;
+        output_stream.set_parameters(parameters);
+        output_stream.set_time_base(stream.time_base());
+        output_stream.set_metadata(stream.metadata().to_owned());
+        // the tag of the source container may not be valid in the clip's
+        unsafe {
+            (*output_stream.parameters().as_mut_ptr()).codec_tag = 0;
+        }
+
+        stream_mapping[index] = Some(output_stream.index());
+        finished[index] = medium == Type::Subtitle;
+    }
+    output_format_context.set_metadata(input_format_context.metadata().to_owned());
+    output_format_context
+        .write_header()
+        .context("failed to write the clip header")?;
+
+    let video_output = stream_mapping[plan.video_index].context("video stream not mapped")?;
+    let mut writer = ClipWriter {
+        output_format_context,
+        offset: plan.start,
+        last_dts: vec![None; stream_mapping.len()],
+        video_index: video_output,
+        video_end: 0.0,
+    };
+
+    if let Some(position) = plan.seek_position {
+        let _ = input_format_context.seek(position, ..position);
+    }
+
+    let video_time_base = time_bases[plan.video_index];
+    let mut keyframes = 0;
+    let mut gop: Option<&Gop> = None;
+
+    for (stream, packet) in input_format_context.packets() {
+        let index = stream.index();
+        let Some(output_index) = stream_mapping[index] else {
+            continue;
+        };
+        let time_base = time_bases[index];
+
+        if index == plan.video_index {
+            if finished[index] {
+                continue;
+            }
+            if keyframe_time(&packet, f64::from(time_base)).is_some() {
+                keyframes += 1;
+                if let (Some(previous), Some(edge)) = (gop, edge.as_mut()) {
+                    if !previous.copy {
+                        edge.finish(&mut writer, plan)?;
+                    }
+                }
+                gop = keyframes
+                    .checked_sub(plan.skipped_gops + 1)
+                    .and_then(|i| plan.gops.get(i));
+                finished[index] = keyframes > plan.skipped_gops + plan.gops.len();
+            }
+
+            match (gop, edge.as_mut()) {
+                (Some(gop), Some(edge)) if !gop.copy => {
+                    edge.send_packet(&packet, &mut writer, plan)?;
+                }
+                (Some(_), _) => match filter.as_mut() {
+                    Some(filter) => {
+                        for packet in filter.filter(packet)? {
+                            writer.write(packet, video_time_base, output_index)?;
+                        }
+                    }
+                    None => writer.write(packet, time_base, output_index)?,
+                },
+                (None, _) => {}
+            }
+        } else {
+            let Some(ts) = packet.pts().or(packet.dts()) else {
+                continue;
+            };
+            let time = ts as f64 * f64::from(time_base);
+            if time >= plan.end {
+                finished[index] = true;
+            } else if time >= plan.start {
+                writer.write(packet, time_base, output_index)?;
+            }
+        }
+
+        if finished.iter().all(|&is_finished| is_finished) {
+            break;
+        }
+    }
+
+    if let (Some(gop), Some(edge)) = (gop, edge.as_mut()) {
+        if !gop.copy && !finished[plan.video_index] {
+            edge.finish(&mut writer, plan)?;
+        }
+    }
+
+    writer
+        .output_format_context
+        .write_trailer()
+        .context("failed to write the clip trailer")?;
+
+    Ok(writer.video_end)
+}
+
+/// Writes packets shifted so the clip starts at zero, keeping the decoding
+/// timestamps of every stream increasing where copied and re-encoded packets
+/// meet.
+struct ClipWriter {
+    output_format_context: format::context::Output,
+    /// Start of the clip in seconds of the source.
+    offset: f64,
+    last_dts: Vec<Option<i64>>,
+    video_index: usize,
+    /// End of the last video packet written, in seconds of the clip.
+    video_end: f64,
+}
+
+impl ClipWriter {
+    fn write(
+        &mut self,
+        mut packet: Packet,
+        time_base: Rational,
+        output_index: usize,
+    ) -> anyhow::Result<()> {
+        let output_time_base = self
+            .output_format_context
+            .stream(output_index)
+            .context("clip stream not found")?
+            .time_base();
+        packet.rescale_ts(time_base, output_time_base);
+
+        let offset = (self.offset / f64::from(output_time_base)).round() as i64;
+        packet.set_pts(packet.pts().map(|pts| pts - offset));
+        packet.set_dts(packet.dts().map(|dts| dts - offset));
+        if let (Some(dts), Some(last_dts)) = (packet.dts(), self.last_dts[output_index]) {
+            if dts <= last_dts {
+                packet.set_dts(Some(last_dts + 1));
+                if packet.pts().is_some_and(|pts| pts <= last_dts) {
+                    packet.set_pts(Some(last_dts + 1));
+                }
+            }
+        }
+        if packet.dts().is_some() {
+            self.last_dts[output_index] = packet.dts();
+        }
+
+        if output_index == self.video_index {
+            if let Some(pts) = packet.pts() {
+                let end = (pts + packet.duration()) as f64 * f64::from(output_time_base);
+                self.video_end = self.video_end.max(end);
+            }
+        }
+
+        packet.set_stream(output_index);
+        packet.set_position(-1);
+        packet
+            .write_interleaved(&mut self.output_format_context)
+            .context("failed to write the clip")?;
+        Ok(())
+    }
+}
+
+/// Decodes the GOPs that cross an edge of the clip and encodes their frames
+/// inside the range with the codec of the source.
+struct EdgeEncoder {
+    decoder: ffmpeg::decoder::Video,
+    encoder: Option<ffmpeg::encoder::video::Encoder>,
+    codec: ffmpeg::Codec,
+    time_base: Rational,
+    frame_rate: Rational,
+    bit_rate: usize,
+}
+
+impl EdgeEncoder {
+    fn new(stream: &format::stream::Stream, threads: usize) -> anyhow::Result<Self> {
+        let parameters = stream.parameters();
+        let codec = ffmpeg::encoder::find(parameters.id()).with_context(|| {
+            format!(
+                "precise cut needs an encoder for {:?}, cut on keyframes instead",
+                parameters.id()
+            )
+        })?;
+        let decoder = decoder::open_decoder(parameters, threads)?;
+        let bit_rate = decoder.bit_rate();
+
+        Ok(Self {
+            decoder,
+            encoder: None,
+            codec,
+            time_base: stream.time_base(),
+            frame_rate: stream.avg_frame_rate(),
+            bit_rate,
+        })
+    }
+
+    fn send_packet(
+        &mut self,
+        packet: &Packet,
+        writer: &mut ClipWriter,
+        plan: &ClipPlan,
+    ) -> anyhow::Result<()> {
+        self.decoder
+            .send_packet(packet)
+            .context("failed to decode the edge of the clip")?;
+        self.receive_frames(writer, plan)
+    }
+
+    /// Drains the decoder and the encoder at the end of a GOP.
+    fn finish(&mut self, writer: &mut ClipWriter, plan: &ClipPlan) -> anyhow::Result<()> {
+        match self.decoder.send_eof() {
+            Ok(()) | Err(ffmpeg::Error::Eof) => {}
+            Err(err) => return Err(err.into()),
+        }
+        self.receive_frames(writer, plan)?;
+        self.decoder.flush();
+
+        if let Some(mut encoder) = self.encoder.take() {
+            encoder.send_eof()?;
+            self.receive_packets(&mut encoder, writer)?;
+        }
+        Ok(())
+    }
+
+    fn receive_frames(&mut self, writer: &mut ClipWriter, plan: &ClipPlan) -> anyhow::Result<()> {
+        let mut decoded = frame::Video::empty();
+        while self.decoder.receive_frame(&mut decoded).is_ok() {
+            let Some(ts) = decoded.timestamp() else {
+                continue;
+            };
+            let time = ts as f64 * f64::from(self.time_base);
+            if time < plan.start || time >= plan.end {
+                continue;
+            }
+
+            let mut encoder = match self.encoder.take() {
+                Some(encoder) => encoder,
+                None => self.open_encoder()?,
+            };
+            decoded.set_pts(Some(ts));
+            decoded.set_kind(ffmpeg::picture::Type::None);
+            encoder
+                .send_frame(&decoded)
+                .context("failed to encode the edge of the clip")?;
+            self.receive_packets(&mut encoder, writer)?;
+            self.encoder = Some(encoder);
+        }
+        Ok(())
+    }
+
+    fn receive_packets(
+        &self,
+        encoder: &mut ffmpeg::encoder::video::Encoder,
+        writer: &mut ClipWriter,
+    ) -> anyhow::Result<()> {
+        let output_index = writer.video_index;
+        loop {
+            let mut encoded = Packet::empty();
+            if encoder.receive_packet(&mut encoded).is_err() {
+                return Ok(());
+            }
+            writer.write(encoded, self.time_base, output_index)?;
+        }
+    }
+
+    /// Opens an encoder for the next edge. Without B-frames, the frames keep
+    /// their order and timestamps.
+    fn open_encoder(&self) -> anyhow::Result<ffmpeg::encoder::video::Encoder> {
+        let context = codec::context::Context::new_with_codec(self.codec);
+        let mut encoder = context.encoder().video()?;
+        encoder.set_width(self.decoder.width());
+        encoder.set_height(self.decoder.height());
+        encoder.set_format(self.decoder.format());
+        encoder.set_aspect_ratio(self.decoder.aspect_ratio());
+        encoder.set_colorspace(self.decoder.color_space());
+        encoder.set_color_range(self.decoder.color_range());
+        encoder.set_time_base(self.time_base);
+        if self.frame_rate.numerator() > 0 {
+            encoder.set_frame_rate(Some(self.frame_rate));
+        }
+        if self.bit_rate > 0 {
+            encoder.set_bit_rate(self.bit_rate);
+        }
+        encoder.set_max_b_frames(0);
+
+        encoder
+            .open_as(self.codec)
+            .with_context(|| format!("failed to open the {:?} encoder", self.codec.id()))
+    }
+}
+
+/// Converts the packets of a stream with an FFmpeg bitstream filter, which
+/// ffmpeg-next does not wrap.
+struct BitstreamFilter(*mut ffmpeg::ffi::AVBSFContext);
+
+impl BitstreamFilter {
+    fn new(
+        name: &str,
+        parameters: &codec::Parameters,
+        time_base: Rational,
+    ) -> anyhow::Result<Self> {
+        let c_name = CString::new(name).context("invalid bitstream filter name")?;
+        unsafe {
+            let bsf = ffmpeg::ffi::av_bsf_get_by_name(c_name.as_ptr());
+            if bsf.is_null() {
+                anyhow::bail!("bitstream filter {} is not available", name);
+            }
+
+            let mut context = ptr::null_mut();
+            check(ffmpeg::ffi::av_bsf_alloc(bsf, &mut context))?;
+            // freed on drop from here on
+            let filter = Self(context);
+            check(ffmpeg::ffi::avcodec_parameters_copy(
+                (*context).par_in,
+                parameters.as_ptr(),
+            ))?;
+            (*context).time_base_in = time_base.into();
+            check(ffmpeg::ffi::av_bsf_init(context))
+                .with_context(|| format!("failed to start bitstream filter {}", name))?;
+            Ok(filter)
+        }
+    }
+
+    /// Parameters of the filtered stream.
+    fn parameters(&self) -> anyhow::Result<codec::Parameters> {
+        let mut parameters = codec::Parameters::new();
+        unsafe {
+            check(ffmpeg::ffi::avcodec_parameters_copy(
+                parameters.as_mut_ptr(),
+                (*self.0).par_out,
+            ))?;
+        }
+        Ok(parameters)
+    }
+
+    fn filter(&mut self, mut packet: Packet) -> anyhow::Result<Vec<Packet>> {
+        let mut filtered = Vec::new();
+        unsafe {
+            check(ffmpeg::ffi::av_bsf_send_packet(self.0, packet.as_mut_ptr()))?;
+            loop {
+                let mut output = Packet::empty();
+                match ffmpeg::ffi::av_bsf_receive_packet(self.0, output.as_mut_ptr()) {
+                    0 => filtered.push(output),
+                    result if result == ffmpeg::ffi::AVERROR(ffmpeg::util::error::EAGAIN) => break,
+                    result => check(result)?,
+                }
+            }
+        }
+        Ok(filtered)
+    }
+}
+
+impl Drop for BitstreamFilter {
+    fn drop(&mut self) {
+        unsafe {
+            ffmpeg::ffi::av_bsf_free(&mut self.0);
+        }
+    }
+}
+
+fn check(result: i32) -> Result<(), ffmpeg::Error> {
+    if result < 0 {
+        Err(ffmpeg::Error::from(result))
+    } else {
+        Ok(())
+    }
+}
+
+fn sha256_file(path: &Path) -> anyhow::Result<String> {
+    let mut file =
+        fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("failed to hash {}", path.display()))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_gops() {
+        let keyframes = [0.0, 2.0, 4.0, 6.0, 8.0];
+        let gops = select_gops(&keyframes, 3.0, 7.0, false);
+        let precise = select_gops(&keyframes, 3.0, 7.0, true);
+
+        // Assert
+        assert_eq!(gops.len(), 3);
+        assert!(gops.iter().all(|gop| gop.copy));
+        assert_eq!(gops[0].start, 2.0);
+        assert_eq!(gops[2].end, Some(8.0));
+        let copied: Vec<bool> = precise.iter().map(|gop| gop.copy).collect();
+        assert_eq!(copied, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_clip_extension() {
+        ffmpeg::init().expect("Failed to init ffmpeg.");
+
+        // Assert
+        assert_eq!(clip_extension(Some("mp4")), "mp4");
+        assert_eq!(clip_extension(Some("mkv")), "mkv");
+        assert_eq!(clip_extension(Some("dav")), DEFAULT_CLIP_EXTENSION);
+        assert_eq!(clip_extension(None), DEFAULT_CLIP_EXTENSION);
+    }
+
+    #[test]
+    fn test_export_clip() {
+        let filename = "../data/video/vid.mp4";
+        let save_path = std::env::temp_dir().join("vimpeg-clip-test");
+        let result = export_clip(
+            Path::new(filename),
+            save_path.as_path(),
+            3.0,
+            10.0,
+            ClipOptions::default(),
+        )
+        .expect("Failed to export the clip.");
+
+        // Assert
+        assert!(result.clip_path.ends_with(".mp4"));
+        assert!(result.start <= 3.0);
+        assert!(result.end >= 10.0);
+        assert_eq!(result.sha256.len(), 64);
+        assert_eq!(
+            sha256_file(Path::new(&result.clip_path)).unwrap(),
+            result.sha256
+        );
+        assert!(Path::new(&result.manifest_path).exists());
+    }
+}
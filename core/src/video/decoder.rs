@@ -1,31 +1,65 @@
-use std::{collections::HashMap, fs, path::Path};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
 
 use anyhow::{self, Context};
 use bytes::Bytes;
-use ffmpeg::{format, media::Type, software::scaling, util::frame};
+use chrono::{DateTime, Local};
+use ffmpeg::{format, media::Type, util::frame};
 use ffmpeg_next::{self as ffmpeg};
-use image::{imageops, DynamicImage, GenericImage, ImageBuffer, Rgba};
+use image::{DynamicImage, GenericImage, ImageBuffer, Rgba, RgbaImage};
 use imageproc::drawing::{draw_text_mut, text_size};
 use rust_embed::RustEmbed;
-
+use serde::Serialize;
+
+use super::{
+    deinterlace::{Deinterlace, Deinterlacer},
+    input::{self, InputOptions},
+    metadata,
+    orientation::Orientation,
+    scaler::Scaler,
+    timeline::Timeline,
+    tonemap::ToneMap,
+};
+
+/// Maximum width and height of the contact sheet tiles.
 const FRAME_DIMENSION: u32 = 300;
 
+/// Default relative position (0.0 - 1.0) of the file where thumbnail sampling begins.
+pub const THUMBNAIL_SAMPLE_POSITION: f64 = 0.1;
+const THUMBNAIL_SAMPLES: usize = 5;
+const THUMBNAIL_MIN_BRIGHTNESS: f64 = 20.0;
+const THUMBNAIL_MIN_CONTRAST: f64 = 10.0;
+const THUMBNAIL_MIN_SHARPNESS: f64 = 15.0;
+/// Seconds of a live stream spread over a contact sheet.
+const LIVE_CONTACT_SHEET_DURATION: f64 = 60.0;
+const LIVE_DEFAULT_FRAME_RATE: f64 = 25.0;
+/// Wait before a capture opens its source again.
+const CAPTURE_RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Snapshots per contact sheet, an hour with more is split over several
+/// sheets so its tiles are not all held in memory.
+const MAX_SHEET_TILES: usize = 144;
+
 #[derive(RustEmbed)]
 #[folder = "../data/fonts/"]
 struct Fonts;
 
 #[derive(Debug)]
-struct FontSettings {
+pub(crate) struct FontSettings {
     font: rusttype::Font<'static>,
     font_scale: rusttype::Scale,
-    font_x: i32,
-    font_y: i32,
 }
 
 #[derive(Debug)]
 pub struct VideoFrame {
     pub data: Bytes,
     pub timestamp: f64,
+    /// Time the frame was picked for, when sampling by time.
+    pub target_timestamp: Option<f64>,
 }
 
 #[derive(Debug, Default)]
@@ -34,6 +68,81 @@ pub struct VideoDump {
     pub height: u32,
     pub nframes: usize,
     pub frames: HashMap<usize, VideoFrame>,
+    pub report: DecodeReport,
+}
+
+/// Damage found while decoding a file in tolerant mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeReport {
+    /// Packets rejected by the decoder.
+    pub corrupt_packets: usize,
+    /// Frames the decoder flagged as corrupt.
+    pub corrupt_frames: usize,
+    /// Jumps in the frame timestamps, such as a recorder restarting its clock.
+    pub discontinuities: usize,
+    /// Decoding stopped on an error before the end of the file.
+    pub failed: bool,
+}
+
+impl DecodeReport {
+    pub fn is_damaged(&self) -> bool {
+        self.corrupt_packets > 0
+            || self.corrupt_frames > 0
+            || self.discontinuities > 0
+            || self.failed
+    }
+
+    /// Adds the damage found in another decoding of the same file.
+    pub fn merge(&mut self, other: &DecodeReport) {
+        self.corrupt_packets += other.corrupt_packets;
+        self.corrupt_frames += other.corrupt_frames;
+        self.discontinuities += other.discontinuities;
+        self.failed |= other.failed;
+    }
+}
+
+/// Decoding stopped on an error of the video stream, as opposed to a
+/// failure to open the file or to save what was extracted.
+#[derive(Debug)]
+pub struct DecodeError {
+    /// Damage found up to the error, with `failed` set.
+    pub report: DecodeReport,
+    source: ffmpeg::Error,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to decode the video stream: {}", self.source)
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Per-job decoding settings.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecodeOptions {
+    /// Overrides the orientation stored in the display matrix of the video
+    /// stream, for files whose rotation metadata is wrong.
+    pub orientation: Option<Orientation>,
+    /// Deinterlacing applied when the stream or its frames are interlaced.
+    pub deinterlace: Deinterlace,
+    /// Operator used to convert HDR frames to SDR.
+    pub tone_map: ToneMap,
+    /// Skips undecodable packets and resynchronises on the next keyframe
+    /// instead of aborting on the first decoding error.
+    pub tolerant: bool,
+    /// Threads used by the codec for frame and slice threading, 0 uses one
+    /// thread per core.
+    pub threads: usize,
+    /// Video track to decode (0 is the first video stream of the file). The
+    /// best video stream is decoded when unset.
+    pub video_track: Option<usize>,
+    /// Demuxer and timing of the input file.
+    pub input: InputOptions,
 }
 
 #[derive(Debug, Default)]
@@ -43,140 +152,501 @@ pub struct VideoThumb {
     pub data: Option<Bytes>,
 }
 
-fn get_font_settings(
+pub(crate) fn get_font_settings(
     frame_width: i32,
     frame_height: i32,
     font_scale: f32,
 ) -> anyhow::Result<FontSettings> {
-    let font =
-        Fonts::get("DejaVuSans.ttf").ok_or_else(|| anyhow::anyhow!("could not load font"))?;
-    let font = font.data.to_vec();
-    let font = rusttype::Font::try_from_vec(font)
-        .ok_or_else(|| anyhow::anyhow!("could not create font"))?;
     let font_height = if frame_height > frame_width {
         (10.0 * font_scale * frame_width as f32) / 360_f32
     } else {
         (5.0 * font_scale * frame_width as f32) / 360_f32
     };
+    get_font_settings_with_height(font_height)
+}
+
+/// Font settings for text `font_height` pixels high, whatever the frame size.
+pub(crate) fn get_font_settings_with_height(font_height: f32) -> anyhow::Result<FontSettings> {
+    let font =
+        Fonts::get("DejaVuSans.ttf").ok_or_else(|| anyhow::anyhow!("could not load font"))?;
+    let font = font.data.to_vec();
+    let font = rusttype::Font::try_from_vec(font)
+        .ok_or_else(|| anyhow::anyhow!("could not create font"))?;
     let font_scale = rusttype::Scale {
         x: font_height * 2.0,
         y: font_height,
     };
-    let font_size = text_size(font_scale, &font, "77:77:77.777");
-    let font_x = frame_width - (font_size.0 + 5);
-    let font_y = frame_height - (font_size.1 + 5);
-
-    Ok(FontSettings {
-        font,
-        font_scale,
-        font_x,
-        font_y,
-    })
+
+    Ok(FontSettings { font, font_scale })
 }
 
-fn draw_timestamp(img: &mut DynamicImage, timestamp: f64, font_settings: &FontSettings) {
+pub(crate) fn draw_timestamp(img: &mut DynamicImage, timestamp: f64, font_settings: &FontSettings) {
     let seconds = timestamp % 60.0;
     let minutes = ((timestamp / 60.0) % 60.0) as u32;
     let hours = ((timestamp / 60.0) / 60.0) as u32;
 
     // put timestamp on image
     let text = format!("{:0>2}:{:0>2}:{:0>6.3}", hours, minutes, seconds);
+    draw_label(img, &text, font_settings);
+}
+
+/// Draws `text` in the bottom right corner of the image.
+fn draw_label(img: &mut DynamicImage, text: &str, font_settings: &FontSettings) {
+    let font_size = text_size(font_settings.font_scale, &font_settings.font, text);
+    let x = img.width() as i32 - (font_size.0 + 5);
+    let y = img.height() as i32 - (font_size.1 + 5);
+    draw_text(img, text, x, y, font_settings);
+}
+
+/// Draws `text` with its top left corner at (x, y).
+pub(crate) fn draw_text(
+    img: &mut DynamicImage,
+    text: &str,
+    x: i32,
+    y: i32,
+    font_settings: &FontSettings,
+) {
     draw_text_mut(
         img,
         image::Rgba([255u8, 111u8, 0u8, 255u8]),
-        font_settings.font_x,
-        font_settings.font_y,
+        x,
+        y,
         font_settings.font_scale,
         &font_settings.font,
-        text.as_str(),
+        text,
     );
 }
 
-pub fn get_thumbnail<P: AsRef<Path>>(video_path: P) -> anyhow::Result<VideoThumb> {
-    ffmpeg::init()?;
-    let mut input_format_context = ffmpeg::format::input(&video_path)?;
+pub fn get_thumbnail<P: AsRef<Path>>(
+    video_path: P,
+    sample_position: f64,
+    deinterlace: Deinterlace,
+    input: InputOptions,
+) -> anyhow::Result<VideoThumb> {
+    let mut input_format_context = input::open_input(&video_path, &input)?;
 
-    let (video_stream_index, mut decoder) = {
+    let (video_stream_index, orientation, mut deinterlacer, mut decoder) = {
         let stream = input_format_context
             .streams()
             .best(Type::Video)
             .ok_or(ffmpeg::Error::StreamNotFound)?;
 
         let stream_index = stream.index();
-        let decode_context = ffmpeg::codec::context::Context::from_parameters(stream.parameters())?;
-        let decoder = decode_context.decoder().video()?;
+        let orientation = Orientation::from_parameters(&stream.parameters());
+        let deinterlacer = Deinterlacer::new(deinterlace, &stream.parameters(), stream.time_base());
+        // thumbnails need only a few frames, which frame threading would delay
+        let decoder = open_decoder(stream.parameters(), 1)?;
 
-        (stream_index, decoder)
+        (stream_index, orientation, deinterlacer, decoder)
     };
 
-    let cover_frame = loop {
-        let mut packet_iter = input_format_context.packets();
-        let cover_packet = loop {
-            match packet_iter.next() {
-                Some((stream, packet)) if stream.index() == video_stream_index => break packet,
-                _ => {}
-            }
-        };
+    let mut scaler = Scaler::new(&decoder);
+
+    // sample a few positions from `sample_position` to the end of the file
+    // and keep the most informative frame. Files without a known duration or
+    // that can not be seeked only have the first decodable frame sampled.
+    let duration = input_format_context.duration();
+    let sample_position = sample_position.clamp(0.0, 0.95);
+    let positions: Vec<i64> = if duration > 0 {
+        let step = (1.0 - sample_position) / THUMBNAIL_SAMPLES as f64;
+        (0..THUMBNAIL_SAMPLES)
+            .map(|i| (duration as f64 * (sample_position + step * i as f64)) as i64)
+            .collect()
+    } else {
+        vec![0]
+    };
 
-        decoder.send_packet(&cover_packet)?;
+    let mut fallback_frame = None;
+    let mut best_frame: Option<(f64, RgbaImage)> = None;
 
-        // repeatedly send packet until a frame can be extracted
-        let mut decoded = frame::Video::empty();
-        if let Ok(()) = decoder.receive_frame(&mut decoded) {
-            break decoded;
+    for position in positions {
+        if position > 0 {
+            if input_format_context.seek(position, ..position).is_err() {
+                break;
+            }
+            decoder.flush();
+            deinterlacer.reset();
         }
-    };
 
-    let scaled_frame = {
-        let mut sws_context = scaling::Context::get(
-            decoder.format(),
-            decoder.width(),
-            decoder.height(),
-            format::Pixel::RGBA,
-            decoder.width(),
-            decoder.height(),
-            scaling::Flags::BILINEAR,
-        )
-        .context("Invalid swscontext parameter.")?;
+        let decoded = match next_frame(
+            &mut input_format_context,
+            video_stream_index,
+            &mut decoder,
+            &mut deinterlacer,
+        )? {
+            Some(decoded) => decoded,
+            None => continue,
+        };
 
-        let mut rgb_frame = frame::Video::empty();
-        sws_context
-            .run(&cover_frame, &mut rgb_frame)
-            .context("Error swscontext run.")?;
+        let img = scaler.run(&decoded)?;
+
+        match (frame_score(&img), &best_frame) {
+            (Some(score), Some((best_score, _))) if score <= *best_score => {}
+            (Some(score), _) => best_frame = Some((score, img)),
+            (None, _) if fallback_frame.is_none() => fallback_frame = Some(img),
+            (None, _) => {}
+        }
+    }
 
-        rgb_frame
+    let img = match best_frame.map(|(_, img)| img).or(fallback_frame) {
+        Some(img) => img,
+        None => {
+            // nothing could be decoded at the sampled positions, so go back
+            // to the first decodable frame of the file
+            input_format_context.seek(0, ..)?;
+            decoder.flush();
+            deinterlacer.reset();
+
+            let decoded = next_frame(
+                &mut input_format_context,
+                video_stream_index,
+                &mut decoder,
+                &mut deinterlacer,
+            )?
+            .ok_or_else(|| anyhow::anyhow!("could not decode any video frame"))?;
+
+            scaler.run(&decoded)?
+        }
     };
 
+    let img = orientation.apply(img);
     let video_thumb = VideoThumb {
-        width: decoder.width(),
-        height: decoder.height(),
-        data: Some(Bytes::from(scaled_frame.data(0).to_owned())),
+        width: img.width(),
+        height: img.height(),
+        data: Some(Bytes::from(img.into_raw())),
     };
 
     Ok(video_thumb)
 }
 
+/// Decodes the next frame of the video stream from the current read position
+/// and runs it through the deinterlacer.
+fn next_frame(
+    input_format_context: &mut format::context::Input,
+    video_stream_index: usize,
+    decoder: &mut ffmpeg::decoder::Video,
+    deinterlacer: &mut Deinterlacer,
+) -> anyhow::Result<Option<frame::Video>> {
+    loop {
+        if let Some((frame, _)) = deinterlacer.pull()? {
+            return Ok(Some(frame));
+        }
+
+        match decode_next_frame(input_format_context, video_stream_index, decoder)? {
+            Some(decoded) => deinterlacer.push(decoded)?,
+            None => {
+                deinterlacer.flush()?;
+                return Ok(deinterlacer.pull()?.map(|(frame, _)| frame));
+            }
+        }
+    }
+}
+
+/// Feeds the decoder and keeps track of the damage found in the stream.
+struct PacketDecoder {
+    tolerant: bool,
+    awaiting_keyframe: bool,
+    report: DecodeReport,
+}
+
+impl PacketDecoder {
+    fn new(tolerant: bool) -> Self {
+        Self {
+            tolerant,
+            awaiting_keyframe: false,
+            report: DecodeReport::default(),
+        }
+    }
+
+    fn send_packet(
+        &mut self,
+        decoder: &mut ffmpeg::decoder::Video,
+        packet: &ffmpeg::Packet,
+    ) -> anyhow::Result<()> {
+        if self.awaiting_keyframe {
+            if !packet.is_key() {
+                return Ok(());
+            }
+            decoder.flush();
+            self.awaiting_keyframe = false;
+        }
+
+        match decoder.send_packet(packet) {
+            Ok(()) => Ok(()),
+            Err(ffmpeg::Error::InvalidData) if self.tolerant => {
+                self.report.corrupt_packets += 1;
+                self.awaiting_keyframe = true;
+                Ok(())
+            }
+            Err(err) => Err(self.error(err)),
+        }
+    }
+
+    fn send_eof(&mut self, decoder: &mut ffmpeg::decoder::Video) -> anyhow::Result<()> {
+        match decoder.send_eof() {
+            Ok(()) => Ok(()),
+            Err(_) if self.tolerant => Ok(()),
+            Err(err) => Err(self.error(err)),
+        }
+    }
+
+    /// Keeps the damage found so far, so the file can still be marked.
+    fn error(&self, source: ffmpeg::Error) -> anyhow::Error {
+        DecodeError {
+            report: DecodeReport {
+                failed: true,
+                ..self.report
+            },
+            source,
+        }
+        .into()
+    }
+
+    /// Moves the frames available in the decoder into the deinterlacer.
+    fn receive_frames(
+        &mut self,
+        decoder: &mut ffmpeg::decoder::Video,
+        deinterlacer: &mut Deinterlacer,
+    ) -> anyhow::Result<()> {
+        let mut decoded = frame::Video::empty();
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            if is_corrupt(&decoded) {
+                self.report.corrupt_frames += 1;
+            }
+            deinterlacer.push(decoded)?;
+            decoded = frame::Video::empty();
+        }
+        Ok(())
+    }
+}
+
+fn is_corrupt(decoded: &frame::Video) -> bool {
+    unsafe {
+        let decoded = &*decoded.as_ptr();
+        decoded.flags & ffmpeg::ffi::AV_FRAME_FLAG_CORRUPT as i32 != 0
+            || decoded.decode_error_flags != 0
+    }
+}
+
+/// Decodes the next frame of the video stream from the current read position.
+fn decode_next_frame(
+    input_format_context: &mut format::context::Input,
+    video_stream_index: usize,
+    decoder: &mut ffmpeg::decoder::Video,
+) -> anyhow::Result<Option<frame::Video>> {
+    let mut decoded = frame::Video::empty();
+
+    for (stream, packet) in input_format_context.packets() {
+        if stream.index() != video_stream_index {
+            continue;
+        }
+
+        // damaged packets are skipped, the thumbnail only needs one good frame
+        if decoder.send_packet(&packet).is_err() {
+            continue;
+        }
+        if decoder.receive_frame(&mut decoded).is_ok() {
+            return Ok(Some(decoded));
+        }
+    }
+
+    // the decoder may already be drained by a previous call
+    match decoder.send_eof() {
+        Ok(()) | Err(ffmpeg::Error::Eof) => {}
+        Err(err) => return Err(err.into()),
+    }
+    if decoder.receive_frame(&mut decoded).is_ok() {
+        return Ok(Some(decoded));
+    }
+
+    Ok(None)
+}
+
+/// Scores how informative an RGBA image is as a thumbnail, combining the
+/// luma histogram entropy with its sharpness (variance of the Laplacian).
+///
+/// Returns `None` for near-black, near-uniform and blurry frames.
+fn frame_score(img: &RgbaImage) -> Option<f64> {
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+    if width < 3 || height < 3 {
+        return None;
+    }
+
+    let stride = width * 4;
+    let data = img.as_raw();
+
+    // analyse a subsampled luma plane to keep scoring cheap on large frames
+    let step = (width.max(height) / 320).max(1);
+    let luma_width = width / step;
+    let luma_height = height / step;
+    let mut luma = Vec::with_capacity(luma_width * luma_height);
+    for y in 0..luma_height {
+        let row = &data[y * step * stride..];
+        for x in 0..luma_width {
+            let pixel = &row[x * step * 4..x * step * 4 + 3];
+            let value = 0.299 * pixel[0] as f64 + 0.587 * pixel[1] as f64 + 0.114 * pixel[2] as f64;
+            luma.push(value);
+        }
+    }
+
+    let count = luma.len() as f64;
+    let mean = luma.iter().sum::<f64>() / count;
+    let std_dev = (luma.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count).sqrt();
+    if mean < THUMBNAIL_MIN_BRIGHTNESS || std_dev < THUMBNAIL_MIN_CONTRAST {
+        return None;
+    }
+
+    let mut laplacian = Vec::with_capacity(luma.len());
+    for y in 1..luma_height - 1 {
+        for x in 1..luma_width - 1 {
+            let i = y * luma_width + x;
+            let value = luma[i - luma_width] + luma[i + luma_width] + luma[i - 1] + luma[i + 1]
+                - 4.0 * luma[i];
+            laplacian.push(value);
+        }
+    }
+    let sharpness = if laplacian.is_empty() {
+        0.0
+    } else {
+        let count = laplacian.len() as f64;
+        let mean = laplacian.iter().sum::<f64>() / count;
+        laplacian.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count
+    };
+    if sharpness < THUMBNAIL_MIN_SHARPNESS {
+        return None;
+    }
+
+    let mut histogram = [0usize; 256];
+    for value in luma.iter() {
+        histogram[(*value as usize).min(255)] += 1;
+    }
+    let entropy = histogram
+        .iter()
+        .filter(|n| **n > 0)
+        .map(|n| {
+            let p = *n as f64 / count;
+            -p * p.log2()
+        })
+        .sum::<f64>();
+
+    Some(entropy * (1.0 + sharpness.ln_1p()))
+}
+
 pub fn dump_video_frames_into_image<P: AsRef<Path>>(
     video_path: P,
     image_path: P,
     cols: usize,
     rows: usize,
     show_timestamp: bool,
-) -> anyhow::Result<()> {
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
     let nframes = rows * cols;
-    let dump = frame_dump(video_path, nframes)?;
+    let dump = frame_dump(video_path, nframes, Some(FRAME_DIMENSION), options)?;
+    let report = dump.report;
     let img = concat_frames(dump, cols, rows, show_timestamp)?;
     img.save(&image_path).context(format!(
         "failed to save image {}",
         image_path.as_ref().display()
     ))?;
-    Ok(())
+    Ok(report)
+}
+
+/// Builds a contact sheet of several video tracks, with the tracks side by
+/// side in every tile so the angles of a time slot can be compared.
+pub fn dump_tracks_into_image<P: AsRef<Path>>(
+    video_path: P,
+    image_path: P,
+    cols: usize,
+    rows: usize,
+    tracks: &[usize],
+    show_timestamp: bool,
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
+    let nframes = rows * cols;
+    let mut report = DecodeReport::default();
+    let mut track_frames = Vec::with_capacity(tracks.len());
+
+    for &track in tracks {
+        let options = DecodeOptions {
+            video_track: Some(track),
+            ..options
+        };
+        let dump = frame_dump(&video_path, nframes, Some(FRAME_DIMENSION), options)?;
+        report.merge(&dump.report);
+        track_frames.push(frames_to_thumbnail(&dump, show_timestamp)?);
+    }
+
+    let mut tiles = Vec::with_capacity(nframes);
+    for i in 0..nframes {
+        let frames: Vec<&DynamicImage> = track_frames
+            .iter()
+            .filter_map(|frames| frames.get(i))
+            .collect();
+        let width = frames.iter().map(|img| img.width()).sum();
+        let height = frames.iter().map(|img| img.height()).max().unwrap_or(0);
+
+        let mut tile = DynamicImage::new_rgba8(width, height);
+        let mut x = 0;
+        for img in frames {
+            tile.copy_from(*img, x, (height - img.height()) / 2)?;
+            x += img.width();
+        }
+        tiles.push(tile);
+    }
+
+    let img = concat_tiles(tiles, cols, rows)?;
+    img.save(&image_path).context(format!(
+        "failed to save image {}",
+        image_path.as_ref().display()
+    ))?;
+    Ok(report)
 }
 
 #[derive(Debug)]
 pub struct VideoDumpResult {
     pub file_name: String,
     pub image_paths: Vec<String>,
+    pub manifest_path: String,
+    pub report: DecodeReport,
+}
+
+/// Records which frame was extracted for every target time.
+#[derive(Debug, Serialize)]
+struct FrameManifest<'a> {
+    video: &'a str,
+    frames: Vec<ManifestFrame>,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestFrame {
+    image: String,
+    target_time: Option<f64>,
+    actual_time: f64,
+}
+
+/// Name of the outputs of a video and the directory under `save_path` they
+/// are saved in, created when missing. Every track of a multi-camera file
+/// gets its own directory.
+pub(crate) fn output_dir<P: AsRef<Path>>(
+    video_path: P,
+    save_path: &Path,
+    video_track: Option<usize>,
+) -> anyhow::Result<(String, PathBuf)> {
+    let video_name = input::media_name(&video_path);
+    if video_name.is_empty() {
+        anyhow::bail!("invalid video path");
+    }
+    let video_name = match video_track {
+        Some(track) => format!("{}_track{}", video_name, track + 1),
+        None => video_name,
+    };
+    let save_path = save_path.join(&video_name);
+    if !save_path.exists() {
+        fs::create_dir_all(&save_path)
+            .with_context(|| format!("Could not create `{}` path", save_path.display()))?;
+    }
+    Ok((video_name, save_path))
 }
 
 pub fn dump_video_frames_by_time<P: AsRef<Path>>(
@@ -186,45 +656,86 @@ pub fn dump_video_frames_by_time<P: AsRef<Path>>(
     time_end: f64,
     frame_rate: u32,
     show_timestamp: bool,
+    options: DecodeOptions,
 ) -> anyhow::Result<VideoDumpResult> {
-    let video_name = video_path
-        .as_ref()
-        .file_stem()
-        .ok_or_else(|| anyhow::anyhow!("invalid video path"))?
-        .to_str()
-        .unwrap_or("");
-    let save_path = save_path.as_ref().join(video_name);
-    if !save_path.exists() {
-        fs::create_dir_all(&save_path)
-            .with_context(|| format!("Could not create `{}` path", save_path.display()))?;
-    }
+    let (video_name, save_path) = output_dir(&video_path, save_path.as_ref(), options.video_track)?;
+    let video_name = video_name.as_str();
 
-    let dump = frame_dump_by_time(&video_path, time_start, time_end, frame_rate)?;
+    let dump = frame_dump_by_time(&video_path, time_start, time_end, frame_rate, options)?;
     let image_paths = frames_to_image(&dump, video_name, &save_path, show_timestamp)?;
+    let manifest_path = save_path.join(format!("{}-manifest.json", video_name));
+    write_manifest(&dump, video_name, &image_paths, &manifest_path)?;
 
     Ok(VideoDumpResult {
         file_name: video_name.to_owned(),
         image_paths,
+        manifest_path: manifest_path.to_str().unwrap_or_default().to_string(),
+        report: dump.report,
     })
 }
 
-pub fn frame_dump<P: AsRef<Path>>(video_path: P, nframes: usize) -> anyhow::Result<VideoDump> {
+fn write_manifest<P: AsRef<Path>>(
+    dump: &VideoDump,
+    video_name: &str,
+    image_paths: &[String],
+    manifest_path: P,
+) -> anyhow::Result<()> {
+    let frames = (0..dump.nframes)
+        .filter_map(|i| dump.frames.get(&i))
+        .zip(image_paths)
+        .map(|(frame, image_path)| ManifestFrame {
+            image: image_path.to_owned(),
+            target_time: frame.target_timestamp,
+            actual_time: frame.timestamp,
+        })
+        .collect();
+    let manifest = FrameManifest {
+        video: video_name,
+        frames,
+    };
+
+    let json = serde_json::to_string_pretty(&manifest).context("failed to serialize manifest")?;
+    fs::write(&manifest_path, json).with_context(|| {
+        format!(
+            "failed to save manifest {}",
+            manifest_path.as_ref().display()
+        )
+    })?;
+    Ok(())
+}
+
+/// Dumps `nframes` frames evenly spread over the video. With `tile_size`,
+/// the frames are scaled to fit in a square of that size.
+pub fn frame_dump<P: AsRef<Path>>(
+    video_path: P,
+    nframes: usize,
+    tile_size: Option<u32>,
+    options: DecodeOptions,
+) -> anyhow::Result<VideoDump> {
     ffmpeg::init()?;
 
-    let options = ffmpeg::Dictionary::new();
-    let mut input_format_context = ffmpeg::format::input_with_dictionary(&video_path, options)?;
+    let mut input_format_context = input::open_input(&video_path, &options.input)?;
 
     // shows a dump of the video
     // let video_path = video_path.as_ref().as_os_str().to_str().unwrap();
     // format::context::input::dump(&input_format_context, 0, Some(video_path));
 
-    let (video_stream_index, frame_rate, time_base, mut decoder) = {
-        let stream = input_format_context
-            .streams()
-            .best(Type::Video)
-            .ok_or(ffmpeg::Error::StreamNotFound)?;
+    // live streams have no end, so the frames are taken from their first
+    // seconds and the reading stops once every tile is filled
+    let is_live = input::is_url(&video_path) && input_format_context.duration() <= 0;
+
+    let (video_stream_index, frame_rate, orientation, mut timeline, mut deinterlacer, mut decoder) = {
+        let stream = select_video_stream(&input_format_context, options.video_track)?;
 
-        let total_frames = if stream.frames() > 0 {
+        let total_frames = if is_live {
+            let rate = stream.avg_frame_rate();
+            let frame_rate = if rate.numerator() > 0 && rate.denominator() > 0 {
+                f64::from(rate)
+            } else {
+                LIVE_DEFAULT_FRAME_RATE
+            };
+            (LIVE_CONTACT_SHEET_DURATION * frame_rate) as i64
+        } else if stream.frames() > 0 {
             stream.frames()
         } else if stream.rate().0 > 0 && input_format_context.duration() > 0 {
             // calculates the number of frames
@@ -238,195 +749,664 @@ pub fn frame_dump<P: AsRef<Path>>(video_path: P, nframes: usize) -> anyhow::Resu
             let nb_frames = duration * frame_rate;
             nb_frames as i64
         } else {
-            0
+            // raw streams and some live recordings do not record the
+            // duration or the frame rate, so the frames are counted instead
+            count_frames(&video_path, stream.index(), &options.input)?
         };
 
-        if nframes as i64 > total_frames {
-            anyhow::bail!(
-                "nframes must be smaller than the total video frames [{}]",
-                total_frames
-            );
-        }
-
-        let frame_rate = total_frames / nframes as i64;
-        let time_base = f64::from(stream.time_base());
+        // files with fewer frames than tiles keep every frame, and the
+        // tiles are filled by repeating them
+        let frame_rate = (total_frames / nframes as i64).max(1);
         let stream_index = stream.index();
-
-        let decode_context = ffmpeg::codec::context::Context::from_parameters(stream.parameters())?;
-        let decoder = decode_context.decoder().video()?;
-
-        (stream_index, frame_rate, time_base, decoder)
+        let orientation = options
+            .orientation
+            .unwrap_or_else(|| Orientation::from_parameters(&stream.parameters()));
+        let deinterlacer = Deinterlacer::new(
+            options.deinterlace,
+            &stream.parameters(),
+            stream.time_base(),
+        );
+
+        let decoder = open_decoder(stream.parameters(), options.threads)?;
+
+        let timeline = Timeline::new(&stream, &options.input);
+
+        (
+            stream_index,
+            frame_rate,
+            orientation,
+            timeline,
+            deinterlacer,
+            decoder,
+        )
     };
 
+    let scaler = Scaler::new(&decoder).with_tone_map(options.tone_map);
+    let mut scaler = match tile_size {
+        Some(tile_size) => scaler.fit_in(tile_size),
+        None => scaler,
+    };
+    let (width, height) = orientation.dimensions(scaler.width(), scaler.height());
     let mut video_dump = VideoDump {
-        width: decoder.width(),
-        height: decoder.height(),
+        width,
+        height,
         ..Default::default()
     };
 
-    let mut sws_context = scaling::Context::get(
-        decoder.format(),
-        decoder.width(),
-        decoder.height(),
-        format::Pixel::RGBA,
-        decoder.width(),
-        decoder.height(),
-        scaling::Flags::BILINEAR,
-    )
-    .context("invalid swscontext parameter")?;
-
     let mut frame_index = 0;
     let mut processed_frames = 0;
 
-    let mut receive_and_process_frames =
-        |decoder: &mut ffmpeg::decoder::Video| -> Result<(), ffmpeg::Error> {
-            let mut decoded = frame::Video::empty();
-
-            while decoder.receive_frame(&mut decoded).is_ok() {
-                if (frame_index < nframes)
-                    && (processed_frames == 0 || processed_frames == frame_rate)
-                {
-                    let mut rgb_frame = frame::Video::empty();
-                    sws_context.run(&decoded, &mut rgb_frame)?;
-
-                    let timestamp = if let Some(timestamp) = decoded.timestamp() {
-                        timestamp as f64 * time_base
-                    } else {
-                        0f64
-                    };
-
-                    let data = rgb_frame.data(0).to_owned();
-                    let video_frame = VideoFrame {
-                        data: Bytes::from(data),
-                        timestamp,
-                    };
-                    video_dump.frames.insert(frame_index, video_frame);
-
-                    frame_index += 1;
-                    processed_frames = 0;
-                }
-
-                processed_frames += 1;
+    // returns whether every tile has a frame
+    let mut process_frames = |deinterlacer: &mut Deinterlacer| -> anyhow::Result<bool> {
+        // field rate deinterlacing outputs more frames than were decoded
+        let frame_rate = frame_rate * i64::from(deinterlacer.rate_factor());
+
+        while let Some((decoded, timestamp)) = deinterlacer.pull()? {
+            let timestamp = timeline.normalize(timestamp, deinterlacer.rate_factor());
+            if (frame_index < nframes) && (processed_frames == 0 || processed_frames == frame_rate)
+            {
+                let data = orientation.apply(scaler.run(&decoded)?).into_raw();
+                let video_frame = VideoFrame {
+                    data: Bytes::from(data),
+                    timestamp,
+                    target_timestamp: None,
+                };
+                video_dump.frames.insert(frame_index, video_frame);
+
+                frame_index += 1;
+                processed_frames = 0;
             }
 
-            Ok(())
-        };
+            processed_frames += 1;
+        }
+
+        Ok(frame_index >= nframes)
+    };
+
+    let mut packet_decoder = PacketDecoder::new(options.tolerant);
+    let mut is_finished = false;
 
     for (stream, packet) in input_format_context.packets() {
         if stream.index() == video_stream_index {
-            decoder.send_packet(&packet)?;
-            receive_and_process_frames(&mut decoder)?;
+            packet_decoder.send_packet(&mut decoder, &packet)?;
+            packet_decoder.receive_frames(&mut decoder, &mut deinterlacer)?;
+            is_finished = process_frames(&mut deinterlacer)?;
+            if is_finished && is_live {
+                break;
+            }
         }
     }
 
-    decoder.send_eof()?;
-    receive_and_process_frames(&mut decoder)?;
+    if !(is_finished && is_live) {
+        packet_decoder.send_eof(&mut decoder)?;
+        packet_decoder.receive_frames(&mut decoder, &mut deinterlacer)?;
+        deinterlacer.flush()?;
+        process_frames(&mut deinterlacer)?;
+    }
 
     video_dump.nframes = frame_index;
+    video_dump.report = packet_decoder.report;
+    video_dump.report.discontinuities = timeline.discontinuities();
+
+    if video_dump.nframes == 0 {
+        anyhow::bail!("could not decode any video frame");
+    }
+    if video_dump.nframes < nframes {
+        repeat_frames(&mut video_dump, nframes);
+    }
 
     Ok(video_dump)
 }
 
+/// Returns the stream of `video_track`, or the best video stream when unset.
+pub(crate) fn select_video_stream(
+    input_format_context: &format::context::Input,
+    video_track: Option<usize>,
+) -> anyhow::Result<format::stream::Stream<'_>> {
+    match video_track {
+        Some(track) => metadata::video_tracks(input_format_context)
+            .nth(track)
+            .with_context(|| format!("video track {} not found", track + 1)),
+        None => Ok(input_format_context
+            .streams()
+            .best(Type::Video)
+            .ok_or(ffmpeg::Error::StreamNotFound)?),
+    }
+}
+
+pub(crate) fn open_decoder(
+    parameters: ffmpeg::codec::Parameters,
+    threads: usize,
+) -> anyhow::Result<ffmpeg::decoder::Video> {
+    let mut decode_context = ffmpeg::codec::context::Context::from_parameters(parameters)?;
+    // only the thread count is set, so the codec keeps both frame and slice
+    // threading when it supports them
+    unsafe {
+        (*decode_context.as_mut_ptr()).thread_count = threads as i32;
+    }
+    let decoder = decode_context
+        .decoder()
+        .video()
+        .context("failed to open the video decoder")?;
+
+    Ok(decoder)
+}
+
+/// Counts the packets of the video stream, demuxing the file without decoding it.
+fn count_frames<P: AsRef<Path>>(
+    video_path: P,
+    video_stream_index: usize,
+    input: &InputOptions,
+) -> anyhow::Result<i64> {
+    let mut input_format_context = input::open_input(&video_path, input)?;
+    let total_frames = input_format_context
+        .packets()
+        .filter(|(stream, _)| stream.index() == video_stream_index)
+        .count();
+
+    Ok(total_frames as i64)
+}
+
+/// Spreads the dumped frames evenly over `nframes` tiles, repeating them.
+fn repeat_frames(video_dump: &mut VideoDump, nframes: usize) {
+    let available = video_dump.nframes;
+    let frames = (0..nframes)
+        .filter_map(|i| {
+            video_dump
+                .frames
+                .get(&(i * available / nframes))
+                .map(|frame| {
+                    (
+                        i,
+                        VideoFrame {
+                            data: frame.data.clone(),
+                            timestamp: frame.timestamp,
+                            target_timestamp: frame.target_timestamp,
+                        },
+                    )
+                })
+        })
+        .collect();
+
+    video_dump.frames = frames;
+    video_dump.nframes = nframes;
+}
+
 pub fn frame_dump_by_time<P: AsRef<Path>>(
     video_path: P,
     time_start: f64,
     time_end: f64,
     frame_rate: u32,
+    options: DecodeOptions,
+) -> anyhow::Result<VideoDump> {
+    if frame_rate == 0 {
+        anyhow::bail!("frame rate must be greater than zero");
+    }
+    let step = 1.0 / frame_rate as f64;
+    frame_dump_by_step(video_path, time_start, time_end, step, None, options)
+}
+
+/// Dumps the frames nearest to every `step` seconds between `time_start` and
+/// `time_end`. With `tile_size`, the frames are scaled to fit in a square of
+/// that size.
+pub(crate) fn frame_dump_by_step<P: AsRef<Path>>(
+    video_path: P,
+    time_start: f64,
+    time_end: f64,
+    step: f64,
+    tile_size: Option<u32>,
+    options: DecodeOptions,
 ) -> anyhow::Result<VideoDump> {
+    let mut video_dump = VideoDump::default();
+    let mut frame_index = 0;
+
+    let report = sample_frames_by_step(
+        video_path,
+        time_start,
+        time_end,
+        step,
+        tile_size,
+        options,
+        &AtomicBool::new(false),
+        |img, timestamp, target| {
+            (video_dump.width, video_dump.height) = img.dimensions();
+            let video_frame = VideoFrame {
+                data: Bytes::from(img.into_raw()),
+                timestamp,
+                target_timestamp: Some(target),
+            };
+            video_dump.frames.insert(frame_index, video_frame);
+            frame_index += 1;
+            Ok(())
+        },
+    )?;
+
+    video_dump.nframes = frame_index;
+    video_dump.report = report;
+
+    Ok(video_dump)
+}
+
+/// Decodes the frames nearest to every `step` seconds between `time_start`
+/// and `time_end`, and hands each one to `on_frame` with its timestamp and
+/// target time. Decoding ends early once `stop` is set.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sample_frames_by_step<P, F>(
+    video_path: P,
+    time_start: f64,
+    time_end: f64,
+    step: f64,
+    tile_size: Option<u32>,
+    options: DecodeOptions,
+    stop: &AtomicBool,
+    mut on_frame: F,
+) -> anyhow::Result<DecodeReport>
+where
+    P: AsRef<Path>,
+    F: FnMut(RgbaImage, f64, f64) -> anyhow::Result<()>,
+{
     ffmpeg::init()?;
 
-    let options = ffmpeg::Dictionary::new();
-    let mut input_format_context = ffmpeg::format::input_with_dictionary(&video_path, options)?;
+    if step <= 0.0 {
+        anyhow::bail!("sampling step must be greater than zero");
+    }
 
-    let (video_stream_index, time_base, frame_rate, mut decoder) = {
-        let stream = input_format_context
-            .streams()
-            .best(Type::Video)
-            .ok_or(ffmpeg::Error::StreamNotFound)?;
+    let mut input_format_context = input::open_input(&video_path, &options.input)?;
 
-        let rate = if stream.rate().denominator() > 0 {
-            stream.rate().numerator() as f64 / stream.rate().denominator() as f64
-        } else {
-            0f64
-        };
+    let (video_stream_index, orientation, mut timeline, mut deinterlacer, mut decoder) = {
+        let stream = select_video_stream(&input_format_context, options.video_track)?;
 
-        let frame_rate = (rate / frame_rate as f64).round() as u32;
-        let time_base = f64::from(stream.time_base());
         let stream_index = stream.index();
-        let decode_context = ffmpeg::codec::context::Context::from_parameters(stream.parameters())?;
-        let decoder = decode_context.decoder().video()?;
-
-        (stream_index, time_base, frame_rate, decoder)
+        let timeline = Timeline::new(&stream, &options.input);
+        let orientation = options
+            .orientation
+            .unwrap_or_else(|| Orientation::from_parameters(&stream.parameters()));
+        let deinterlacer = Deinterlacer::new(
+            options.deinterlace,
+            &stream.parameters(),
+            stream.time_base(),
+        );
+        let decoder = open_decoder(stream.parameters(), options.threads)?;
+
+        (stream_index, orientation, timeline, deinterlacer, decoder)
     };
 
-    let mut video_dump = VideoDump {
-        width: decoder.width(),
-        height: decoder.height(),
-        ..Default::default()
+    let scaler = Scaler::new(&decoder).with_tone_map(options.tone_map);
+    let mut scaler = match tile_size {
+        Some(tile_size) => scaler.fit_in(tile_size),
+        None => scaler,
     };
 
-    let mut sws_context = scaling::Context::get(
-        decoder.format(),
-        decoder.width(),
-        decoder.height(),
-        format::Pixel::RGBA,
-        decoder.width(),
-        decoder.height(),
-        scaling::Flags::BILINEAR,
-    )
-    .context("invalid swscontext parameter")?;
+    // start at the keyframe before `time_start` rather than decoding the
+    // whole prefix. Files that can not be seeked are decoded from the start.
+    // `time_start` is relative to the first frame, while seeking uses the
+    // timestamps stored in the file.
+    if let Some(position) = timeline.seek_position(time_start) {
+        let _ = input_format_context.seek(position, ..position);
+    }
 
-    let mut frame_index = 0;
-    let mut processed_frames = 0;
+    let mut keep_frame =
+        |decoded: &frame::Video, timestamp: f64, target: f64| -> anyhow::Result<()> {
+            let img = orientation.apply(scaler.run(decoded)?);
+            on_frame(img, timestamp, target)
+        };
 
-    let mut receive_and_process_frames =
-        |decoder: &mut ffmpeg::decoder::Video| -> Result<(), ffmpeg::Error> {
-            let mut decoded = frame::Video::empty();
-
-            while decoder.receive_frame(&mut decoded).is_ok() {
-                if processed_frames == 0 || processed_frames == frame_rate {
-                    let timestamp = if let Some(timestamp) = decoded.timestamp() {
-                        timestamp as f64 * time_base
-                    } else {
-                        0f64
-                    };
-
-                    if timestamp >= time_start && timestamp <= time_end {
-                        let mut rgb_frame = frame::Video::empty();
-                        sws_context.run(&decoded, &mut rgb_frame)?;
-
-                        let data = rgb_frame.data(0).to_owned();
-                        let video_frame = VideoFrame {
-                            data: Bytes::from(data),
-                            timestamp,
-                        };
-                        video_dump.frames.insert(frame_index, video_frame);
-
-                        frame_index += 1;
+    // the frames nearest to a uniform grid of target times are kept, so the
+    // spacing holds for variable frame rate videos. Targets without a frame
+    // closer than one step are left out rather than filled with a far frame.
+    let target_at = |index: usize| time_start + index as f64 * step;
+    let mut target_index = 0;
+    let mut previous: Option<(frame::Video, f64)> = None;
+
+    // returns whether every target time has been handled
+    let mut process_frames = |deinterlacer: &mut Deinterlacer| -> anyhow::Result<bool> {
+        while let Some((decoded, timestamp)) = deinterlacer.pull()? {
+            let timestamp = timeline.normalize(timestamp, deinterlacer.rate_factor());
+            while target_at(target_index) < time_end && timestamp >= target_at(target_index) {
+                let target = target_at(target_index);
+                let (nearest, nearest_timestamp) = match previous.as_ref() {
+                    Some((frame, frame_timestamp))
+                        if target - frame_timestamp <= timestamp - target =>
+                    {
+                        (frame, *frame_timestamp)
                     }
-
-                    processed_frames = 0;
+                    _ => (&decoded, timestamp),
+                };
+                if (nearest_timestamp - target).abs() <= step {
+                    keep_frame(nearest, nearest_timestamp, target)?;
                 }
-
-                processed_frames += 1;
+                target_index += 1;
             }
 
-            Ok(())
-        };
+            previous = Some((decoded, timestamp));
+        }
+
+        Ok(target_at(target_index) >= time_end)
+    };
+
+    let mut packet_decoder = PacketDecoder::new(options.tolerant);
+    let mut is_finished = false;
 
     for (stream, packet) in input_format_context.packets() {
+        if stop.load(Ordering::Relaxed) {
+            is_finished = true;
+            break;
+        }
         if stream.index() == video_stream_index {
-            decoder.send_packet(&packet)?;
-            receive_and_process_frames(&mut decoder)?;
+            packet_decoder.send_packet(&mut decoder, &packet)?;
+            packet_decoder.receive_frames(&mut decoder, &mut deinterlacer)?;
+            is_finished = process_frames(&mut deinterlacer)?;
+            if is_finished {
+                break;
+            }
+        }
+    }
+
+    if !is_finished {
+        packet_decoder.send_eof(&mut decoder)?;
+        packet_decoder.receive_frames(&mut decoder, &mut deinterlacer)?;
+        deinterlacer.flush()?;
+        process_frames(&mut deinterlacer)?;
+
+        // targets after the last frame of the file
+        if let Some((frame, timestamp)) = previous.as_ref() {
+            while target_at(target_index) < time_end
+                && (timestamp - target_at(target_index)).abs() <= step
+            {
+                keep_frame(frame, *timestamp, target_at(target_index))?;
+                target_index += 1;
+            }
         }
     }
 
-    decoder.send_eof()?;
-    receive_and_process_frames(&mut decoder)?;
+    let mut report = packet_decoder.report;
+    report.discontinuities = timeline.discontinuities();
 
-    video_dump.nframes = frame_index;
+    Ok(report)
+}
 
-    Ok(video_dump)
+/// Settings of a periodic snapshot capture.
+#[derive(Debug, Clone, Copy)]
+pub struct CaptureOptions {
+    /// Seconds between two snapshots.
+    pub interval: u32,
+    /// Stamps the snapshots with the wall-clock time they were taken.
+    pub show_timestamp: bool,
+    /// Rolls the snapshots of every hour into a contact sheet.
+    pub hourly_sheet: bool,
+    pub decode: DecodeOptions,
+}
+
+/// Progress of a capture, reported as it runs.
+#[derive(Debug)]
+pub enum CaptureEvent {
+    /// Path of a saved snapshot.
+    Snapshot(String),
+    /// Path of the contact sheet of an hour.
+    ContactSheet(String),
+    /// The source failed or ended, and is opened again.
+    Interrupted(String),
+}
+
+#[derive(Debug, Default)]
+pub struct CaptureResult {
+    pub file_name: String,
+    pub image_paths: Vec<String>,
+    pub sheet_paths: Vec<String>,
+}
+
+/// Snapshots taken so far and the tiles of the current hour.
+struct Capture {
+    video_name: String,
+    save_path: PathBuf,
+    options: CaptureOptions,
+    next_snapshot: Instant,
+    sheet_hour: Option<String>,
+    /// Sheets already saved for the current hour.
+    sheet_part: usize,
+    sheet_tiles: Vec<DynamicImage>,
+    result: CaptureResult,
+}
+
+impl Capture {
+    /// Saves a snapshot taken at `now`, named and stamped with that time, and
+    /// adds it to the contact sheet of its hour.
+    fn save_snapshot(
+        &mut self,
+        img: RgbaImage,
+        now: DateTime<Local>,
+        on_event: &mut impl FnMut(CaptureEvent),
+    ) -> anyhow::Result<()> {
+        let mut img = DynamicImage::ImageRgba8(img);
+
+        let hour = now.format("%Y%m%d-%H").to_string();
+        if self.sheet_hour.as_ref() != Some(&hour) {
+            self.save_sheet(on_event)?;
+            self.sheet_hour = Some(hour);
+            self.sheet_part = 0;
+        } else if self.sheet_tiles.len() == MAX_SHEET_TILES {
+            self.save_sheet(on_event)?;
+        }
+        if self.options.hourly_sheet {
+            let mut tile = img.thumbnail(FRAME_DIMENSION, FRAME_DIMENSION);
+            if let Some(first) = self.sheet_tiles.first() {
+                // the stream may change its resolution within the hour
+                if (tile.width(), tile.height()) != (first.width(), first.height()) {
+                    tile = tile.resize_exact(
+                        first.width(),
+                        first.height(),
+                        image::imageops::FilterType::Triangle,
+                    );
+                }
+            }
+            if self.options.show_timestamp {
+                let font_settings =
+                    get_font_settings(tile.width() as i32, tile.height() as i32, 2.0)?;
+                draw_label(
+                    &mut tile,
+                    &now.format("%H:%M:%S").to_string(),
+                    &font_settings,
+                );
+            }
+            self.sheet_tiles.push(tile);
+        }
+
+        if self.options.show_timestamp {
+            let font_settings = get_font_settings(img.width() as i32, img.height() as i32, 0.7)?;
+            draw_label(
+                &mut img,
+                &now.format("%Y-%m-%d %H:%M:%S").to_string(),
+                &font_settings,
+            );
+        }
+        let image_path = self.save_path.join(format!(
+            "{}-{}.jpeg",
+            self.video_name,
+            now.format("%Y%m%d-%H%M%S")
+        ));
+        img.save(&image_path)
+            .with_context(|| format!("failed to save image {}", image_path.display()))?;
+
+        let image_path = image_path.to_str().unwrap().to_string();
+        on_event(CaptureEvent::Snapshot(image_path.clone()));
+        self.result.image_paths.push(image_path);
+
+        Ok(())
+    }
+
+    /// Saves the contact sheet of the current hour, in a grid as square as
+    /// the number of snapshots allows. Later sheets of the same hour are
+    /// numbered from 2.
+    fn save_sheet(&mut self, on_event: &mut impl FnMut(CaptureEvent)) -> anyhow::Result<()> {
+        let Some(hour) = self.sheet_hour.as_ref() else {
+            return Ok(());
+        };
+        if self.sheet_tiles.is_empty() {
+            return Ok(());
+        }
+
+        let tiles = std::mem::take(&mut self.sheet_tiles);
+        let cols = (tiles.len() as f64).sqrt().ceil() as usize;
+        let rows = tiles.len().div_ceil(cols);
+        let sheet = concat_tiles(tiles, cols, rows)?;
+
+        self.sheet_part += 1;
+        let sheet_name = match self.sheet_part {
+            1 => format!("hourly_{}_{}.jpeg", self.video_name, hour),
+            part => format!("hourly_{}_{}_{}.jpeg", self.video_name, hour, part),
+        };
+        let sheet_path = self.save_path.join(sheet_name);
+        sheet
+            .save(&sheet_path)
+            .with_context(|| format!("failed to save image {}", sheet_path.display()))?;
+
+        let sheet_path = sheet_path.to_str().unwrap().to_string();
+        on_event(CaptureEvent::ContactSheet(sheet_path.clone()));
+        self.result.sheet_paths.push(sheet_path);
+
+        Ok(())
+    }
+
+    /// Reads the source until it ends or `stop` is set, taking a snapshot
+    /// every interval.
+    fn run<P: AsRef<Path>>(
+        &mut self,
+        video_path: P,
+        stop: &AtomicBool,
+        on_event: &mut impl FnMut(CaptureEvent),
+    ) -> anyhow::Result<()> {
+        let options = self.options.decode;
+        let is_url = input::is_url(&video_path);
+        // a growing file is read from its current end, and waited on there
+        let input = InputOptions {
+            follow: !is_url,
+            ..options.input
+        };
+        let backlog = if is_url {
+            0
+        } else {
+            fs::metadata(&video_path)
+                .with_context(|| format!("failed to read {}", video_path.as_ref().display()))?
+                .len() as i64
+        };
+
+        let mut input_format_context = input::open_input_until(&video_path, &input, stop)?;
+
+        let (video_stream_index, orientation, mut deinterlacer, mut decoder) = {
+            let stream = select_video_stream(&input_format_context, options.video_track)?;
+
+            let stream_index = stream.index();
+            let orientation = options
+                .orientation
+                .unwrap_or_else(|| Orientation::from_parameters(&stream.parameters()));
+            let deinterlacer = Deinterlacer::new(
+                options.deinterlace,
+                &stream.parameters(),
+                stream.time_base(),
+            );
+            let decoder = open_decoder(stream.parameters(), options.threads)?;
+
+            (stream_index, orientation, deinterlacer, decoder)
+        };
+
+        let mut scaler = Scaler::new(&decoder).with_tone_map(options.tone_map);
+        let mut packet_decoder = PacketDecoder::new(options.tolerant);
+        let interval = Duration::from_secs(u64::from(self.options.interval));
+        let mut is_backlog = backlog > 0;
+
+        // `packets()` retries on read errors, which never ends once the
+        // reading is interrupted or the connection is lost
+        let mut packet = ffmpeg::Packet::empty();
+        loop {
+            match packet.read(&mut input_format_context) {
+                Ok(()) => {}
+                Err(ffmpeg::Error::Eof) => break,
+                Err(_) if stop.load(Ordering::Relaxed) => break,
+                Err(ffmpeg::Error::InvalidData) => continue,
+                Err(err) => return Err(err).context("failed to read the stream"),
+            }
+            if packet.stream() != video_stream_index {
+                continue;
+            }
+            if is_backlog {
+                // decoding starts on the first keyframe written after the
+                // capture started
+                let position = packet.position() as i64;
+                if (position >= 0 && position < backlog) || !packet.is_key() {
+                    continue;
+                }
+                is_backlog = false;
+            }
+
+            packet_decoder.send_packet(&mut decoder, &packet)?;
+            packet_decoder.receive_frames(&mut decoder, &mut deinterlacer)?;
+            while let Some((decoded, _)) = deinterlacer.pull()? {
+                let now = Instant::now();
+                if now < self.next_snapshot {
+                    continue;
+                }
+                while self.next_snapshot <= now {
+                    self.next_snapshot += interval;
+                }
+
+                let img = orientation.apply(scaler.run(&decoded)?);
+                self.save_snapshot(img, Local::now(), on_event)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Takes a snapshot every `interval` seconds from a live stream or a file
+/// still being written, until `stop` is set. Sources that fail or end are
+/// opened again, and the last contact sheet is saved when the capture stops.
+pub fn capture_snapshots<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    options: CaptureOptions,
+    stop: &AtomicBool,
+    mut on_event: impl FnMut(CaptureEvent),
+) -> anyhow::Result<CaptureResult> {
+    if options.interval == 0 {
+        anyhow::bail!("capture interval must be greater than zero");
+    }
+
+    let (video_name, save_path) = output_dir(&video_path, save_path.as_ref(), None)?;
+
+    let mut capture = Capture {
+        video_name: video_name.clone(),
+        save_path,
+        options,
+        next_snapshot: Instant::now(),
+        sheet_hour: None,
+        sheet_part: 0,
+        sheet_tiles: Vec::new(),
+        result: CaptureResult {
+            file_name: video_name,
+            ..Default::default()
+        },
+    };
+
+    while !stop.load(Ordering::Relaxed) {
+        let error = match capture.run(&video_path, stop, &mut on_event) {
+            Ok(()) => anyhow::anyhow!("end of stream"),
+            Err(err) => err,
+        };
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        on_event(CaptureEvent::Interrupted(format!("{:#}", error)));
+
+        let retry = Instant::now() + CAPTURE_RETRY_DELAY;
+        while Instant::now() < retry && !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    capture.save_sheet(&mut on_event)?;
+
+    Ok(capture.result)
 }
 
 fn concat_frames(
@@ -436,6 +1416,14 @@ fn concat_frames(
     show_timestamp: bool,
 ) -> anyhow::Result<DynamicImage> {
     let frames = frames_to_thumbnail(&dump, show_timestamp)?;
+    concat_tiles(frames, cols, rows)
+}
+
+pub(crate) fn concat_tiles(
+    frames: Vec<DynamicImage>,
+    cols: usize,
+    rows: usize,
+) -> anyhow::Result<DynamicImage> {
     let img_width_out: u32 = frames.iter().map(|img| img.width()).take(cols).sum();
     let img_height_out: u32 = frames.iter().map(|img| img.height()).take(rows).sum();
 
@@ -460,7 +1448,7 @@ fn concat_frames(
     Ok(dynamic_img)
 }
 
-fn frames_to_thumbnail(
+pub(crate) fn frames_to_thumbnail(
     dump: &VideoDump,
     show_timestamp: bool,
 ) -> anyhow::Result<Vec<DynamicImage>> {
@@ -476,15 +1464,12 @@ fn frames_to_thumbnail(
                     .unwrap();
             let mut img = DynamicImage::ImageRgba8(img_buf);
 
+            // the frames are already at tile size, so the overlay is drawn
+            // at the final resolution
             if show_timestamp {
                 draw_timestamp(&mut img, frame.timestamp, &font_settings);
             }
 
-            let img = img.resize(
-                FRAME_DIMENSION,
-                FRAME_DIMENSION,
-                imageops::FilterType::Lanczos3,
-            );
             frames.push(img);
         }
     }
@@ -533,42 +1518,105 @@ fn frames_to_image<P: AsRef<Path>>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_video_thumbnail() {
         let filename = "../data/video/vid.mp4";
-        let video_thumb = get_thumbnail(filename).expect("Failed to get thumbnail.");
+        let video_thumb = get_thumbnail(
+            filename,
+            THUMBNAIL_SAMPLE_POSITION,
+            Deinterlace::Off,
+            InputOptions::default(),
+        )
+        .expect("Failed to get thumbnail.");
 
         // Assert
         assert_ne!(video_thumb.data, None);
     }
 
+    #[test]
+    fn test_frame_score() {
+        let black = RgbaImage::from_pixel(64, 48, Rgba([0, 0, 0, 255]));
+        let flat = RgbaImage::from_pixel(64, 48, Rgba([128, 128, 128, 255]));
+        let textured = RgbaImage::from_fn(64, 48, |x, y| {
+            let value = ((x * 7 + y * 13) % 9 * 25 + 20) as u8;
+            Rgba([value, value, value, 255])
+        });
+
+        // Assert
+        assert_eq!(frame_score(&black), None);
+        assert_eq!(frame_score(&flat), None);
+        assert!(frame_score(&textured).is_some_and(|score| score > 0.0));
+    }
+
     #[test]
     fn test_video_frame_dump() {
         let filename = "../data/video/vid.mp4";
-        let video_dump = frame_dump(filename, 36).expect("Failed to dump frame.");
+        let video_dump = frame_dump(
+            filename,
+            36,
+            Some(FRAME_DIMENSION),
+            DecodeOptions::default(),
+        )
+        .expect("Failed to dump frame.");
 
         // Assert
         assert_eq!(video_dump.nframes, 36);
+        assert!(video_dump.width <= FRAME_DIMENSION && video_dump.height <= FRAME_DIMENSION);
+        assert!(video_dump.width == FRAME_DIMENSION || video_dump.height == FRAME_DIMENSION);
+    }
+
+    #[test]
+    fn test_video_frame_dump_tolerant() {
+        let filename = "../data/video/vid.mp4";
+        let options = DecodeOptions {
+            tolerant: true,
+            ..Default::default()
+        };
+        let video_dump = frame_dump(filename, 36, None, options).expect("Failed to dump frame.");
+
+        // Assert
+        assert_eq!(video_dump.nframes, 36);
+        assert!(!video_dump.report.is_damaged());
+    }
+
+    #[test]
+    fn test_video_frame_dump_missing_track() {
+        let filename = "../data/video/vid.mp4";
+        let options = DecodeOptions {
+            video_track: Some(1),
+            ..Default::default()
+        };
+        let result = frame_dump(filename, 4, None, options);
+
+        // Assert
+        assert!(result.is_err());
     }
 
     #[test]
     fn test_video_frame_dump_by_time() {
         let filename = "../data/video/vid.mp4";
-        let video_dump =
-            frame_dump_by_time(filename, 3.0, 10.0, 15).expect("Failed to dump frame.");
+        let video_dump = frame_dump_by_time(filename, 3.0, 10.0, 15, DecodeOptions::default())
+            .expect("Failed to dump frame.");
 
         // Assert
         assert_eq!(video_dump.nframes, 105);
+        assert!(video_dump.frames.values().all(|frame| {
+            let target = frame.target_timestamp.unwrap_or_default();
+            (frame.timestamp - target).abs() <= 1.0 / 15.0
+        }));
     }
 
     #[test]
-    fn test_video_frame_dump_error() {
+    fn test_video_frame_dump_repeated_tiles() {
         let filename = "../data/video/vid.mp4";
-        let is_error = frame_dump(filename, 400).is_err();
+        let video_dump = frame_dump(filename, 400, None, DecodeOptions::default())
+            .expect("Failed to dump frame.");
 
         // Assert
-        assert_eq!(is_error, true);
+        assert_eq!(video_dump.nframes, 400);
+        assert_eq!(video_dump.frames.len(), 400);
     }
 
     #[test]
@@ -578,10 +1626,68 @@ mod tests {
         let rows: usize = 6;
         let nframes = cols * rows;
 
-        let dump = frame_dump(filename, nframes).expect("Failed to dump frame.");
+        let dump = frame_dump(
+            filename,
+            nframes,
+            Some(FRAME_DIMENSION),
+            DecodeOptions::default(),
+        )
+        .expect("Failed to dump frame.");
         let _ = concat_frames(dump, cols, rows, true).expect("Failed to concat frames.");
 
         // Assert
         assert!(true);
     }
+
+    #[test]
+    fn test_capture_hourly_sheet() {
+        let save_path =
+            std::env::temp_dir().join(format!("vimpeg-capture-test-{}", std::process::id()));
+        fs::create_dir_all(&save_path).expect("Failed to create the capture path.");
+        let mut capture = Capture {
+            video_name: "cam1".to_owned(),
+            save_path: save_path.clone(),
+            options: CaptureOptions {
+                interval: 1,
+                show_timestamp: true,
+                hourly_sheet: true,
+                decode: DecodeOptions::default(),
+            },
+            next_snapshot: Instant::now(),
+            sheet_hour: None,
+            sheet_part: 0,
+            sheet_tiles: Vec::new(),
+            result: CaptureResult::default(),
+        };
+        let mut sheets = 0;
+        let mut on_event = |event| {
+            if let CaptureEvent::ContactSheet(_) = event {
+                sheets += 1;
+            }
+        };
+
+        // three snapshots in one hour and one in the next
+        let start = Local.with_ymd_and_hms(2024, 1, 31, 10, 59, 57).unwrap();
+        for seconds in 0..4 {
+            capture
+                .save_snapshot(
+                    RgbaImage::new(640, 360),
+                    start + chrono::Duration::seconds(seconds),
+                    &mut on_event,
+                )
+                .expect("Failed to save the snapshot.");
+        }
+        capture
+            .save_sheet(&mut on_event)
+            .expect("Failed to save the contact sheet.");
+        let sheet = image::open(&capture.result.sheet_paths[0]).expect("Failed to open the sheet.");
+        fs::remove_dir_all(&save_path).expect("Failed to remove the capture path.");
+
+        // Assert
+        assert_eq!(capture.result.image_paths.len(), 4);
+        assert_eq!(sheets, 2);
+        assert!(capture.result.sheet_paths[0].ends_with("hourly_cam1_20240131-10.jpeg"));
+        assert!(capture.result.sheet_paths[1].ends_with("hourly_cam1_20240131-11.jpeg"));
+        assert_eq!(sheet.width(), 2 * FRAME_DIMENSION);
+    }
 }
@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+
+use anyhow::{self, Context};
+use ffmpeg::{filter, util::frame};
+use ffmpeg_next::{self as ffmpeg};
+use serde::{Deserialize, Serialize};
+
+/// Deinterlacing applied to interlaced streams before scaling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Deinterlace {
+    #[default]
+    Off,
+    /// Every field as its own frame, lines doubled without interpolation.
+    Fields,
+    /// Every field as its own frame, missing lines interpolated (bwdif).
+    Bob,
+    /// One motion adaptive frame per frame (bwdif).
+    Quality,
+}
+
+impl Deinterlace {
+    fn filter_spec(&self) -> Option<&'static str> {
+        match self {
+            Deinterlace::Off => None,
+            Deinterlace::Fields => Some("separatefields,scale=w=iw:h=ih*2:flags=neighbor"),
+            Deinterlace::Bob => Some("bwdif=mode=send_field:parity=auto:deint=all"),
+            Deinterlace::Quality => Some("bwdif=mode=send_frame:parity=auto:deint=all"),
+        }
+    }
+
+    /// Number of output frames for every decoded frame.
+    pub fn rate_factor(&self) -> u32 {
+        match self {
+            Deinterlace::Fields | Deinterlace::Bob => 2,
+            Deinterlace::Off | Deinterlace::Quality => 1,
+        }
+    }
+}
+
+/// Whether the field order stored in the stream parameters marks it as interlaced.
+pub(crate) fn is_interlaced_stream(parameters: &ffmpeg::codec::Parameters) -> bool {
+    use ffmpeg::ffi::AVFieldOrder::*;
+
+    let field_order = unsafe { (*parameters.as_ptr()).field_order };
+    matches!(
+        field_order,
+        AV_FIELD_TT | AV_FIELD_BB | AV_FIELD_TB | AV_FIELD_BT
+    )
+}
+
+pub(crate) fn field_order_name(parameters: &ffmpeg::codec::Parameters) -> Option<&'static str> {
+    use ffmpeg::ffi::AVFieldOrder::*;
+
+    match unsafe { (*parameters.as_ptr()).field_order } {
+        AV_FIELD_PROGRESSIVE => Some("progressive"),
+        AV_FIELD_TT => Some("tt"),
+        AV_FIELD_BB => Some("bb"),
+        AV_FIELD_TB => Some("tb"),
+        AV_FIELD_BT => Some("bt"),
+        _ => None,
+    }
+}
+
+/// Runs decoded frames through the deinterlacing filter graph.
+///
+/// Whether the filter is used is decided on the first frame, from the field
+/// order of the stream and the interlaced flag of the frame. Otherwise the
+/// frames are passed through untouched.
+pub(crate) struct Deinterlacer {
+    mode: Deinterlace,
+    interlaced_stream: bool,
+    time_base: ffmpeg::Rational,
+    active: Option<bool>,
+    graph: Option<filter::Graph>,
+    pending: VecDeque<frame::Video>,
+}
+
+impl Deinterlacer {
+    pub(crate) fn new(
+        mode: Deinterlace,
+        parameters: &ffmpeg::codec::Parameters,
+        time_base: ffmpeg::Rational,
+    ) -> Self {
+        Self {
+            mode,
+            interlaced_stream: is_interlaced_stream(parameters),
+            time_base,
+            active: None,
+            graph: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Number of output frames for every decoded frame.
+    pub(crate) fn rate_factor(&self) -> u32 {
+        match self.active {
+            Some(true) => self.mode.rate_factor(),
+            _ => 1,
+        }
+    }
+
+    /// Drops the buffered frames, after a seek.
+    pub(crate) fn reset(&mut self) {
+        self.graph = None;
+        self.pending.clear();
+    }
+
+    pub(crate) fn push(&mut self, mut decoded: frame::Video) -> anyhow::Result<()> {
+        let is_interlaced = self.interlaced_stream || decoded.is_interlaced();
+        let active = *self
+            .active
+            .get_or_insert(self.mode != Deinterlace::Off && is_interlaced);
+        if !active {
+            self.pending.push_back(decoded);
+            return Ok(());
+        }
+
+        if self.graph.is_none() {
+            self.graph = Some(self.create_graph(&decoded)?);
+        }
+        if let Some(graph) = self.graph.as_mut() {
+            decoded.set_pts(decoded.timestamp());
+            graph
+                .get("in")
+                .context("missing filter source")?
+                .source()
+                .add(&decoded)
+                .context("failed to feed the deinterlacing filter")?;
+        }
+
+        Ok(())
+    }
+
+    /// Signals the end of the stream, so the filter releases the buffered frames.
+    pub(crate) fn flush(&mut self) -> anyhow::Result<()> {
+        if let Some(graph) = self.graph.as_mut() {
+            graph
+                .get("in")
+                .context("missing filter source")?
+                .source()
+                .flush()
+                .context("failed to flush the deinterlacing filter")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the next output frame with its timestamp in seconds.
+    pub(crate) fn pull(&mut self) -> anyhow::Result<Option<(frame::Video, f64)>> {
+        let graph = match self.graph.as_mut() {
+            Some(graph) => graph,
+            None => {
+                let time_base = f64::from(self.time_base);
+                return Ok(self.pending.pop_front().map(|decoded| {
+                    let timestamp = decoded
+                        .timestamp()
+                        .map_or(0f64, |timestamp| timestamp as f64 * time_base);
+                    (decoded, timestamp)
+                }));
+            }
+        };
+
+        let mut sink = graph.get("out").context("missing filter sink")?;
+        let time_base = f64::from(ffmpeg::Rational::from(unsafe {
+            ffmpeg::ffi::av_buffersink_get_time_base(sink.as_ptr())
+        }));
+
+        let mut filtered = frame::Video::empty();
+        match sink.sink().frame(&mut filtered) {
+            Ok(()) => {
+                let timestamp = filtered
+                    .pts()
+                    .map_or(0f64, |timestamp| timestamp as f64 * time_base);
+                Ok(Some((filtered, timestamp)))
+            }
+            Err(ffmpeg::Error::Eof) => Ok(None),
+            Err(ffmpeg::Error::Other { errno }) if errno == ffmpeg::error::EAGAIN => Ok(None),
+            Err(err) => Err(err).context("failed to read the deinterlacing filter"),
+        }
+    }
+
+    fn create_graph(&self, decoded: &frame::Video) -> anyhow::Result<filter::Graph> {
+        let spec = self
+            .mode
+            .filter_spec()
+            .context("deinterlacing is disabled")?;
+
+        let aspect_ratio = match decoded.aspect_ratio() {
+            ratio if ratio.numerator() > 0 && ratio.denominator() > 0 => ratio,
+            _ => ffmpeg::Rational::new(1, 1),
+        };
+        let args = format!(
+            "video_size={}x{}:pix_fmt={}:time_base={}/{}:pixel_aspect={}/{}",
+            decoded.width(),
+            decoded.height(),
+            ffmpeg::ffi::AVPixelFormat::from(decoded.format()) as i32,
+            self.time_base.numerator(),
+            self.time_base.denominator(),
+            aspect_ratio.numerator(),
+            aspect_ratio.denominator(),
+        );
+
+        let mut graph = filter::Graph::new();
+        graph.add(
+            &filter::find("buffer").context("missing buffer filter")?,
+            "in",
+            &args,
+        )?;
+        graph.add(
+            &filter::find("buffersink").context("missing buffersink filter")?,
+            "out",
+            "",
+        )?;
+        graph.output("in", 0)?.input("out", 0)?.parse(spec)?;
+        graph
+            .validate()
+            .context("invalid deinterlacing filter graph")?;
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deinterlace_rate_factor() {
+        assert_eq!(Deinterlace::Off.rate_factor(), 1);
+        assert_eq!(Deinterlace::Fields.rate_factor(), 2);
+        assert_eq!(Deinterlace::Bob.rate_factor(), 2);
+        assert_eq!(Deinterlace::Quality.rate_factor(), 1);
+    }
+}
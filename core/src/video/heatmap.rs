@@ -0,0 +1,232 @@
+use std::{path::Path, sync::atomic::AtomicBool};
+
+use anyhow::{self, Context};
+use image::{DynamicImage, GenericImage, GrayImage, Rgba, RgbaImage};
+
+use super::decoder::{self, DecodeOptions, DecodeReport};
+
+/// Largest width and height of the analysed frames and of the heatmap.
+const ANALYSIS_SIZE: u32 = 640;
+/// Luma difference between two samples above which a pixel has changed.
+/// Sensor noise and compression stay below it.
+const CHANGE_THRESHOLD: u8 = 25;
+/// Height of the activity timeline drawn under the heatmap.
+const TIMELINE_HEIGHT: u32 = 120;
+const TIMELINE_BACKGROUND: Rgba<u8> = Rgba([32, 32, 32, 255]);
+
+/// Saves a heatmap of the pixel change accumulated over the video, drawn on
+/// its average frame, with the activity over time in a strip at the bottom.
+pub fn dump_motion_heatmap<P: AsRef<Path>>(
+    video_path: P,
+    image_path: P,
+    interval: u32,
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
+    if interval == 0 {
+        anyhow::bail!("sampling interval must be greater than zero");
+    }
+
+    let mut activity = ActivityMap::default();
+    let report = decoder::sample_frames_by_step(
+        &video_path,
+        0.0,
+        f64::INFINITY,
+        f64::from(interval),
+        Some(ANALYSIS_SIZE),
+        options,
+        &AtomicBool::new(false),
+        |img, timestamp, _| {
+            activity.add(&img, timestamp);
+            Ok(())
+        },
+    )?;
+    if activity.samples == 0 {
+        anyhow::bail!("could not decode any video frame");
+    }
+
+    let img = activity.render()?;
+    img.save(&image_path).context(format!(
+        "failed to save image {}",
+        image_path.as_ref().display()
+    ))?;
+    Ok(report)
+}
+
+/// Change counted per pixel between consecutive samples.
+#[derive(Debug, Default)]
+struct ActivityMap {
+    width: u32,
+    height: u32,
+    samples: usize,
+    /// Sum of the RGB values of every sample, for the average frame.
+    background: Vec<u64>,
+    /// Times every pixel changed.
+    changes: Vec<u32>,
+    previous: Option<GrayImage>,
+    /// Timestamp and share of changed pixels of every sample.
+    timeline: Vec<(f64, f64)>,
+}
+
+impl ActivityMap {
+    fn add(&mut self, img: &RgbaImage, timestamp: f64) {
+        if self.samples == 0 {
+            (self.width, self.height) = img.dimensions();
+            let pixels = (self.width * self.height) as usize;
+            self.background = vec![0; pixels * 3];
+            self.changes = vec![0; pixels];
+        } else if img.dimensions() != (self.width, self.height) {
+            // a resolution change mid-stream can not be compared
+            return;
+        }
+        self.samples += 1;
+
+        for (sum, pixel) in self.background.chunks_exact_mut(3).zip(img.pixels()) {
+            for (sum, &value) in sum.iter_mut().zip(&pixel.0[..3]) {
+                *sum += u64::from(value);
+            }
+        }
+
+        let luma = DynamicImage::ImageRgba8(img.clone()).into_luma8();
+        let changed = match self.previous.as_ref() {
+            Some(previous) => {
+                let mut changed = 0usize;
+                for ((count, a), b) in self
+                    .changes
+                    .iter_mut()
+                    .zip(previous.as_raw())
+                    .zip(luma.as_raw())
+                {
+                    if a.abs_diff(*b) > CHANGE_THRESHOLD {
+                        *count += 1;
+                        changed += 1;
+                    }
+                }
+                changed as f64 / self.changes.len().max(1) as f64
+            }
+            None => 0.0,
+        };
+        self.timeline.push((timestamp, changed));
+        self.previous = Some(luma);
+    }
+
+    fn render(&self) -> anyhow::Result<RgbaImage> {
+        let mut img = RgbaImage::new(self.width, self.height + TIMELINE_HEIGHT);
+        img.copy_from(&self.heatmap(), 0, 0)?;
+        img.copy_from(&self.timeline_strip()?, 0, self.height)?;
+        Ok(img)
+    }
+
+    /// Average frame with the pixels that changed tinted from blue, rarely,
+    /// to red, most often.
+    fn heatmap(&self) -> RgbaImage {
+        let samples = self.samples.max(1) as u64;
+        let max_changes = self.changes.iter().copied().max().unwrap_or(0).max(1);
+
+        let mut img = RgbaImage::new(self.width, self.height);
+        for ((pixel, sum), &count) in img
+            .pixels_mut()
+            .zip(self.background.chunks_exact(3))
+            .zip(&self.changes)
+        {
+            let background = [
+                (sum[0] / samples) as u8,
+                (sum[1] / samples) as u8,
+                (sum[2] / samples) as u8,
+            ];
+            *pixel = if count == 0 {
+                Rgba([background[0], background[1], background[2], 255])
+            } else {
+                // the square root keeps rare changes visible next to busy areas
+                let level = (count as f64 / max_changes as f64).sqrt();
+                blend(background, heat_color(level), 0.35 + 0.5 * level)
+            };
+        }
+        img
+    }
+
+    /// Share of changed pixels over time, with the length of the video in
+    /// the corner.
+    fn timeline_strip(&self) -> anyhow::Result<RgbaImage> {
+        let mut strip = RgbaImage::from_pixel(self.width, TIMELINE_HEIGHT, TIMELINE_BACKGROUND);
+        let duration = self
+            .timeline
+            .last()
+            .map_or(0.0, |&(timestamp, _)| timestamp);
+        let max_activity = self
+            .timeline
+            .iter()
+            .map(|&(_, activity)| activity)
+            .fold(0.0, f64::max);
+
+        if duration > 0.0 && max_activity > 0.0 {
+            for &(timestamp, activity) in &self.timeline {
+                let x = ((timestamp / duration) * f64::from(self.width - 1)) as u32;
+                let level = activity / max_activity;
+                let bar_height = (level * f64::from(TIMELINE_HEIGHT - 4)).round() as u32;
+                for y in TIMELINE_HEIGHT - bar_height..TIMELINE_HEIGHT {
+                    strip.put_pixel(x, y, heat_color(level));
+                }
+            }
+        }
+
+        let mut strip = DynamicImage::ImageRgba8(strip);
+        let font_settings =
+            decoder::get_font_settings(self.width as i32, TIMELINE_HEIGHT as i32, 1.0)?;
+        decoder::draw_timestamp(&mut strip, duration, &font_settings);
+        Ok(strip.into_rgba8())
+    }
+}
+
+/// Color of a level from 0 to 1 on a blue, green, yellow, red scale.
+fn heat_color(level: f64) -> Rgba<u8> {
+    let level = level.clamp(0.0, 1.0);
+    let (r, g, b) = if level < 1.0 / 3.0 {
+        let t = level * 3.0;
+        (0.0, t, 1.0 - t)
+    } else if level < 2.0 / 3.0 {
+        let t = (level - 1.0 / 3.0) * 3.0;
+        (t, 1.0, 0.0)
+    } else {
+        let t = (level - 2.0 / 3.0) * 3.0;
+        (1.0, 1.0 - t, 0.0)
+    };
+    Rgba([(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, 255])
+}
+
+fn blend(background: [u8; 3], color: Rgba<u8>, alpha: f64) -> Rgba<u8> {
+    let mix = |a: u8, b: u8| (f64::from(a) * (1.0 - alpha) + f64::from(b) * alpha) as u8;
+    Rgba([
+        mix(background[0], color[0]),
+        mix(background[1], color[1]),
+        mix(background[2], color[2]),
+        255,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_map() {
+        let still = RgbaImage::from_pixel(8, 8, Rgba([0, 0, 0, 255]));
+        let mut moved = still.clone();
+        for y in 0..4 {
+            for x in 0..4 {
+                moved.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+
+        let mut activity = ActivityMap::default();
+        activity.add(&still, 0.0);
+        activity.add(&moved, 1.0);
+        activity.add(&moved, 2.0);
+        let img = activity.render().expect("Failed to render the heatmap.");
+
+        // Assert
+        assert_eq!(activity.changes[0], 1);
+        assert_eq!(activity.changes[63], 0);
+        assert_eq!(activity.timeline, vec![(0.0, 0.0), (1.0, 0.25), (2.0, 0.0)]);
+        assert_eq!(img.dimensions(), (8, 8 + TIMELINE_HEIGHT));
+    }
+}
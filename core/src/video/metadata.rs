@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, ffi::CStr, path::Path};
+
+use anyhow::{self, Context};
+use ffmpeg::{format, media::Type};
+use ffmpeg_next::{self as ffmpeg};
+use serde::Serialize;
+
+use super::{
+    deinterlace,
+    input::{self, InputOptions},
+};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MediaInfo {
+    pub format_name: String,
+    pub format_description: String,
+    pub duration: f64,
+    pub bit_rate: i64,
+    pub video_stream_index: Option<usize>,
+    pub tags: BTreeMap<String, String>,
+    pub streams: Vec<StreamInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StreamInfo {
+    pub index: usize,
+    pub media_type: String,
+    pub codec: String,
+    pub profile: Option<String>,
+    pub pixel_format: Option<String>,
+    pub bit_rate: i64,
+    pub language: Option<String>,
+    pub duration: f64,
+    pub frame_rate: f64,
+    pub nb_frames: i64,
+    pub width: u32,
+    pub height: u32,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub rotation: f64,
+    pub field_order: Option<String>,
+    /// Position among the video tracks of the file, for track selection.
+    pub track: Option<usize>,
+    pub color: Option<ColorInfo>,
+    pub tags: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ColorInfo {
+    pub range: Option<String>,
+    pub space: Option<String>,
+    pub primaries: Option<String>,
+    pub transfer: Option<String>,
+}
+
+impl MediaInfo {
+    /// Returns the stream selected for decoding, if the file has any video.
+    pub fn video_stream(&self) -> Option<&StreamInfo> {
+        let index = self.video_stream_index?;
+        self.streams.iter().find(|stream| stream.index == index)
+    }
+
+    /// Returns the video streams that can be selected as tracks, in track order.
+    pub fn video_tracks(&self) -> impl Iterator<Item = &StreamInfo> {
+        self.streams.iter().filter(|stream| stream.track.is_some())
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        serde_json::to_string_pretty(self).context("failed to serialize media info")
+    }
+}
+
+/// Reads the media info of a file opened with the demuxer and options of `input`.
+pub fn get_media_info_with_input<P: AsRef<Path>>(
+    video_path: P,
+    input: &InputOptions,
+) -> anyhow::Result<MediaInfo> {
+    let context = input::open_input(&video_path, input)?;
+
+    let mut media_info = MediaInfo {
+        format_name: context.format().name().to_owned(),
+        format_description: context.format().description().to_owned(),
+        // duration (seconds), unknown for live streams
+        duration: context.duration().max(0) as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE),
+        bit_rate: context.bit_rate(),
+        video_stream_index: context.streams().best(Type::Video).map(|s| s.index()),
+        tags: collect_tags(context.metadata()),
+        ..Default::default()
+    };
+
+    let tracks: Vec<usize> = video_tracks(&context)
+        .map(|stream| stream.index())
+        .collect();
+    for stream in context.streams() {
+        // a stream with unreadable parameters is left out, the others are still listed
+        let Ok(mut stream_info) = stream_info(&stream) else {
+            continue;
+        };
+        stream_info.track = tracks.iter().position(|&index| index == stream.index());
+        media_info.streams.push(stream_info);
+    }
+
+    Ok(media_info)
+}
+
+/// Video streams of the file in order, leaving out cover art stored as a
+/// video stream. Multi-camera recordings keep one stream per camera.
+pub(crate) fn video_tracks(
+    context: &format::context::Input,
+) -> impl Iterator<Item = format::stream::Stream<'_>> {
+    context.streams().filter(|stream| {
+        stream.parameters().medium() == Type::Video
+            && !stream
+                .disposition()
+                .contains(format::stream::Disposition::ATTACHED_PIC)
+    })
+}
+
+fn stream_info(stream: &format::stream::Stream) -> anyhow::Result<StreamInfo> {
+    let parameters = stream.parameters();
+    let (bit_rate, profile) = unsafe {
+        let parameters = &*parameters.as_ptr();
+        (parameters.bit_rate, parameters.profile)
+    };
+    let tags = collect_tags(stream.metadata());
+
+    let mut stream_info = StreamInfo {
+        index: stream.index(),
+        media_type: media_type_name(parameters.medium()).to_owned(),
+        codec: parameters.id().name().to_owned(),
+        profile: profile_name(parameters.id(), profile),
+        bit_rate,
+        language: tags.get("language").cloned(),
+        duration: if stream.duration() > 0 {
+            stream.duration() as f64 * f64::from(stream.time_base())
+        } else {
+            0f64
+        },
+        frame_rate: if stream.rate().denominator() > 0 {
+            f64::from(stream.rate())
+        } else {
+            0f64
+        },
+        nb_frames: stream.frames(),
+        rotation: display_rotation(&parameters),
+        tags,
+        ..Default::default()
+    };
+
+    let codec = ffmpeg::codec::context::Context::from_parameters(parameters)?;
+    match codec.medium() {
+        Type::Video => {
+            if let Ok(video) = codec.decoder().video() {
+                stream_info.width = video.width();
+                stream_info.height = video.height();
+                stream_info.field_order =
+                    deinterlace::field_order_name(&stream.parameters()).map(str::to_owned);
+                stream_info.pixel_format = video
+                    .format()
+                    .descriptor()
+                    .map(|descriptor| descriptor.name().to_owned());
+                stream_info.color = Some(ColorInfo {
+                    range: video.color_range().name().map(str::to_owned),
+                    space: video.color_space().name().map(str::to_owned),
+                    primaries: video.color_primaries().name().map(str::to_owned),
+                    transfer: video
+                        .color_transfer_characteristic()
+                        .name()
+                        .map(str::to_owned),
+                });
+            }
+        }
+        Type::Audio => {
+            if let Ok(audio) = codec.decoder().audio() {
+                stream_info.sample_rate = audio.rate();
+                stream_info.channels = audio.channels();
+            }
+        }
+        _ => {}
+    }
+
+    Ok(stream_info)
+}
+
+/// Returns the display matrix stored in the stream side data, if any.
+pub(crate) fn display_matrix(parameters: &ffmpeg::codec::Parameters) -> Option<[i32; 9]> {
+    unsafe {
+        let parameters = &*parameters.as_ptr();
+        let side_data = ffmpeg::ffi::av_packet_side_data_get(
+            parameters.coded_side_data,
+            parameters.nb_coded_side_data,
+            ffmpeg::ffi::AVPacketSideDataType::AV_PKT_DATA_DISPLAYMATRIX,
+        );
+        if side_data.is_null() || (*side_data).size < 36 {
+            return None;
+        }
+
+        let mut matrix = [0i32; 9];
+        std::ptr::copy_nonoverlapping((*side_data).data as *const i32, matrix.as_mut_ptr(), 9);
+        Some(matrix)
+    }
+}
+
+/// Clockwise rotation in degrees (0 - 360) that must be applied to the
+/// decoded frames to display them upright.
+pub(crate) fn display_rotation(parameters: &ffmpeg::codec::Parameters) -> f64 {
+    match display_matrix(parameters) {
+        Some(matrix) => matrix_rotation(&matrix),
+        None => 0f64,
+    }
+}
+
+pub(crate) fn matrix_rotation(matrix: &[i32; 9]) -> f64 {
+    let rotation = unsafe { ffmpeg::ffi::av_display_rotation_get(matrix.as_ptr()) };
+    if rotation.is_nan() {
+        return 0f64;
+    }
+
+    let mut theta = -rotation.round();
+    theta -= 360.0 * (theta / 360.0 + 0.9 / 360.0).floor();
+    theta
+}
+
+fn profile_name(codec_id: ffmpeg::codec::Id, profile: i32) -> Option<String> {
+    unsafe {
+        let name = ffmpeg::ffi::avcodec_profile_name(codec_id.into(), profile);
+        if name.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+        }
+    }
+}
+
+fn media_type_name(media_type: Type) -> &'static str {
+    match media_type {
+        Type::Video => "video",
+        Type::Audio => "audio",
+        Type::Subtitle => "subtitle",
+        Type::Data => "data",
+        Type::Attachment => "attachment",
+        Type::Unknown => "unknown",
+    }
+}
+
+fn collect_tags(dictionary: ffmpeg::DictionaryRef) -> BTreeMap<String, String> {
+    dictionary
+        .iter()
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_info() {
+        let filename = "../data/video/vid.mp4";
+        let media_info = get_media_info_with_input(filename, &InputOptions::default())
+            .expect("Failed to get media info.");
+
+        // Assert
+        assert!(media_info.video_stream().is_some());
+        assert!(media_info.to_json().is_ok());
+    }
+}
@@ -1,5 +1,26 @@
+mod clip;
 mod decoder;
+mod deinterlace;
+mod heatmap;
+mod input;
+mod metadata;
+mod orientation;
+mod preview;
+mod scaler;
 mod search;
+mod sequence;
 pub mod service;
+mod slitscan;
+mod sprite;
+mod stack;
+mod timelapse;
+mod timeline;
+mod tonemap;
+mod writer;
 
+pub use deinterlace::Deinterlace;
+pub use input::{is_url, media_name, InputFormat, InputOptions};
+pub use metadata::{ColorInfo, MediaInfo, StreamInfo};
+pub use orientation::Orientation;
 pub use search::Video;
+pub use tonemap::ToneMap;
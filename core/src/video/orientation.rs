@@ -0,0 +1,104 @@
+use image::{imageops, RgbaImage};
+
+use super::metadata;
+use ffmpeg_next::{self as ffmpeg};
+
+/// Transformation applied to the decoded frames: a clockwise rotation
+/// followed by the horizontal and vertical flips.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Orientation {
+    pub rotation: u32,
+    pub hflip: bool,
+    pub vflip: bool,
+}
+
+impl Orientation {
+    pub fn new(rotation: u32, hflip: bool, vflip: bool) -> Self {
+        Self {
+            rotation: rotation % 360,
+            hflip,
+            vflip,
+        }
+    }
+
+    /// Orientation stored in the display matrix of the video stream, mapped
+    /// the same way the ffmpeg command line tool does it.
+    pub(crate) fn from_parameters(parameters: &ffmpeg::codec::Parameters) -> Self {
+        match metadata::display_matrix(parameters) {
+            Some(matrix) => Self::from_display_matrix(&matrix),
+            None => Self::default(),
+        }
+    }
+
+    pub(crate) fn from_display_matrix(matrix: &[i32; 9]) -> Self {
+        let theta = metadata::matrix_rotation(matrix);
+
+        if (theta - 90.0).abs() < 1.0 {
+            Self::new(90, matrix[3] > 0, false)
+        } else if (theta - 180.0).abs() < 1.0 {
+            Self::new(0, matrix[0] < 0, matrix[4] < 0)
+        } else if (theta - 270.0).abs() < 1.0 {
+            Self::new(270, matrix[3] < 0, false)
+        } else if theta.abs() < 1.0 {
+            Self::new(0, false, matrix[4] < 0)
+        } else {
+            Self::default()
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.rotation == 0 && !self.hflip && !self.vflip
+    }
+
+    /// Frame dimensions after the orientation is applied.
+    pub fn dimensions(&self, width: u32, height: u32) -> (u32, u32) {
+        match self.rotation {
+            90 | 270 => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    pub(crate) fn apply(&self, img: RgbaImage) -> RgbaImage {
+        if self.is_identity() {
+            return img;
+        }
+
+        let mut img = match self.rotation {
+            90 => imageops::rotate90(&img),
+            180 => imageops::rotate180(&img),
+            270 => imageops::rotate270(&img),
+            _ => img,
+        };
+        if self.hflip {
+            imageops::flip_horizontal_in_place(&mut img);
+        }
+        if self.vflip {
+            imageops::flip_vertical_in_place(&mut img);
+        }
+        img
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orientation_dimensions() {
+        let orientation = Orientation::new(90, false, false);
+        assert_eq!(orientation.dimensions(1920, 1080), (1080, 1920));
+
+        let orientation = Orientation::new(180, true, false);
+        assert_eq!(orientation.dimensions(1920, 1080), (1920, 1080));
+    }
+
+    #[test]
+    fn test_orientation_apply() {
+        let img = RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+        let rotated = Orientation::new(90, false, false).apply(img);
+
+        // Assert
+        assert_eq!(rotated.dimensions(), (2, 3));
+        assert_eq!(rotated.get_pixel(1, 0).0, [0, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,289 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use anyhow::{self, Context};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+    Delay, DynamicImage, Frame, ImageBuffer, RgbaImage,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    decoder::{self, DecodeOptions, DecodeReport, FontSettings, VideoDump},
+    writer::{VideoWriter, MP4_ENCODERS, WEBP_ENCODERS},
+};
+
+/// Largest width and height of a preview.
+const DEFAULT_PREVIEW_SIZE: u32 = 480;
+/// Frames shown per second.
+const DEFAULT_PREVIEW_RATE: u32 = 2;
+
+/// File format of an animated preview.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    #[default]
+    Gif,
+    Webp,
+    Mp4,
+}
+
+impl PreviewFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PreviewFormat::Gif => "gif",
+            PreviewFormat::Webp => "webp",
+            PreviewFormat::Mp4 => "mp4",
+        }
+    }
+}
+
+/// How sampled frames are assembled into an animation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewOptions {
+    /// Chosen per extraction, only the size and rate are preferences.
+    #[serde(skip)]
+    pub format: PreviewFormat,
+    /// Largest width and height of the frames.
+    pub size: u32,
+    /// Frames shown per second.
+    pub frame_rate: u32,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            format: PreviewFormat::Gif,
+            size: DEFAULT_PREVIEW_SIZE,
+            frame_rate: DEFAULT_PREVIEW_RATE,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PreviewResult {
+    pub file_name: String,
+    pub preview_path: String,
+    pub report: DecodeReport,
+}
+
+/// Saves `nframes` frames spread over the video, the frames of a contact
+/// sheet, as an animated preview.
+pub fn dump_video_preview<P: AsRef<Path>>(
+    video_path: P,
+    preview_path: P,
+    nframes: usize,
+    show_timestamp: bool,
+    preview: PreviewOptions,
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
+    let dump = decoder::frame_dump(&video_path, nframes, Some(preview.size), options)?;
+    save_preview(&dump, &preview_path, show_timestamp, &preview)?;
+    Ok(dump.report)
+}
+
+/// Saves the frames sampled at `frame_rate` between `time_start` and
+/// `time_end` as an animated preview in a directory named after the video.
+/// The frames are decoded at preview size and encoded as they arrive.
+#[allow(clippy::too_many_arguments)]
+pub fn dump_video_preview_by_time<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    time_start: f64,
+    time_end: f64,
+    frame_rate: u32,
+    show_timestamp: bool,
+    preview: PreviewOptions,
+    options: DecodeOptions,
+) -> anyhow::Result<PreviewResult> {
+    if frame_rate == 0 {
+        anyhow::bail!("frame rate must be greater than zero");
+    }
+
+    let (video_name, save_path) =
+        decoder::output_dir(&video_path, save_path.as_ref(), options.video_track)?;
+    let preview_path = save_path.join(format!(
+        "{}-preview.{}",
+        video_name,
+        preview.format.extension()
+    ));
+
+    let mut writer = PreviewWriter::new(&preview_path, show_timestamp, preview)?;
+    let report = decoder::sample_frames_by_step(
+        &video_path,
+        time_start,
+        time_end,
+        1.0 / f64::from(frame_rate),
+        Some(preview.size),
+        options,
+        &AtomicBool::new(false),
+        |img, timestamp, _| writer.write(img, timestamp),
+    );
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            // closes the file, so what was encoded stays playable
+            let _ = writer.finish();
+            return Err(err);
+        }
+    };
+    writer.finish()?;
+
+    Ok(PreviewResult {
+        file_name: video_name,
+        preview_path: preview_path.to_string_lossy().to_string(),
+        report,
+    })
+}
+
+fn save_preview<P: AsRef<Path>>(
+    dump: &VideoDump,
+    preview_path: P,
+    show_timestamp: bool,
+    preview: &PreviewOptions,
+) -> anyhow::Result<()> {
+    let mut writer = PreviewWriter::new(preview_path.as_ref(), show_timestamp, *preview)?;
+    for i in 0..dump.nframes {
+        let Some(frame) = dump.frames.get(&i) else {
+            continue;
+        };
+        let img = ImageBuffer::from_raw(dump.width, dump.height, frame.data.to_vec())
+            .context("invalid frame size")?;
+        writer.write(img, frame.timestamp)?;
+    }
+    writer.finish()
+}
+
+/// Encoder of the preview, opened on the first frame.
+enum PreviewOutput {
+    Gif(GifEncoder<io::BufWriter<fs::File>>),
+    Video(VideoWriter),
+}
+
+/// Encodes the frames of a preview one by one, fitted in a square of `size`
+/// and resized to the first frame if the stream changes its resolution, with
+/// the timestamp drawn at the final resolution.
+struct PreviewWriter {
+    path: PathBuf,
+    show_timestamp: bool,
+    preview: PreviewOptions,
+    frame_size: Option<(u32, u32)>,
+    font_settings: Option<FontSettings>,
+    output: Option<PreviewOutput>,
+}
+
+impl PreviewWriter {
+    fn new(path: &Path, show_timestamp: bool, preview: PreviewOptions) -> anyhow::Result<Self> {
+        if preview.frame_rate == 0 {
+            anyhow::bail!("preview frame rate must be greater than zero");
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            show_timestamp,
+            preview,
+            frame_size: None,
+            font_settings: None,
+            output: None,
+        })
+    }
+
+    fn write(&mut self, img: RgbaImage, timestamp: f64) -> anyhow::Result<()> {
+        let size = self.preview.size;
+        let mut img = DynamicImage::ImageRgba8(img);
+        if img.width() > size || img.height() > size {
+            img = img.resize(size, size, FilterType::Triangle);
+        }
+        let (width, height) = *self.frame_size.get_or_insert((img.width(), img.height()));
+        if (img.width(), img.height()) != (width, height) {
+            img = img.resize_exact(width, height, FilterType::Triangle);
+        }
+
+        if self.show_timestamp && self.font_settings.is_none() {
+            self.font_settings = Some(decoder::get_font_settings(
+                width as i32,
+                height as i32,
+                2.0,
+            )?);
+        }
+        if let Some(font_settings) = self.font_settings.as_ref() {
+            decoder::draw_timestamp(&mut img, timestamp, font_settings);
+        }
+
+        if self.output.is_none() {
+            self.output = Some(self.open(width, height)?);
+        }
+        let img = img.into_rgba8();
+        match self.output.as_mut() {
+            Some(PreviewOutput::Gif(encoder)) => {
+                let delay = Delay::from_numer_denom_ms(1000, self.preview.frame_rate);
+                encoder.encode_frame(Frame::from_parts(img, 0, 0, delay))?;
+            }
+            Some(PreviewOutput::Video(writer)) => writer.write(&img)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn open(&self, width: u32, height: u32) -> anyhow::Result<PreviewOutput> {
+        let encoders = match self.preview.format {
+            PreviewFormat::Gif => {
+                let file = fs::File::create(&self.path)?;
+                let mut encoder = GifEncoder::new_with_speed(io::BufWriter::new(file), 10);
+                encoder.set_repeat(Repeat::Infinite)?;
+                return Ok(PreviewOutput::Gif(encoder));
+            }
+            PreviewFormat::Webp => WEBP_ENCODERS,
+            PreviewFormat::Mp4 => MP4_ENCODERS,
+        };
+        // the first available encoder, in the container given by the
+        // extension of the path
+        let writer =
+            VideoWriter::new(&self.path, encoders, width, height, self.preview.frame_rate)?;
+        Ok(PreviewOutput::Video(writer))
+    }
+
+    /// Flushes the encoder and closes the file.
+    fn finish(self) -> anyhow::Result<()> {
+        let result = match self.output {
+            None => Err(anyhow::anyhow!("no frames to put in the preview")),
+            // the GIF trailer is written when the encoder is dropped
+            Some(PreviewOutput::Gif(_)) => Ok(()),
+            Some(PreviewOutput::Video(writer)) => writer.finish(),
+        };
+        result.with_context(|| format!("failed to save preview {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_video_preview_gif() {
+        let filename = "../data/video/vid.mp4";
+        let preview_path = std::env::temp_dir().join("vimpeg-preview-test.gif");
+        let preview = PreviewOptions {
+            size: 160,
+            ..Default::default()
+        };
+        dump_video_preview(
+            Path::new(filename),
+            preview_path.as_path(),
+            8,
+            true,
+            preview,
+            DecodeOptions::default(),
+        )
+        .expect("Failed to save the preview.");
+
+        let img = image::open(&preview_path).expect("Failed to open the preview.");
+
+        // Assert
+        assert!(img.width() <= 160 && img.height() <= 160);
+    }
+}
@@ -0,0 +1,259 @@
+use anyhow::{self, Context};
+use ffmpeg::{format, software::scaling, util::color, util::frame};
+use ffmpeg_next::{self as ffmpeg};
+use image::RgbaImage;
+
+use super::tonemap::{HdrTransfer, ToneMap, ToneMapper};
+
+/// Converts decoded frames to RGBA at their display size, honouring the
+/// sample aspect ratio, colour matrix and colour range of the source.
+///
+/// HDR frames are converted to 16 bit RGB and tone mapped to SDR.
+pub(crate) struct Scaler {
+    width: u32,
+    height: u32,
+    flags: scaling::Flags,
+    tone_map: ToneMap,
+    /// Transfer function of the stream, for frames without colour metadata.
+    stream_transfer: color::TransferCharacteristic,
+    context: Option<(Source, scaling::Context)>,
+    tone_mapper: Option<ToneMapper>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Source {
+    format: format::Pixel,
+    width: u32,
+    height: u32,
+    color_space: color::Space,
+    color_range: color::Range,
+    hdr_transfer: Option<HdrTransfer>,
+}
+
+impl Scaler {
+    /// Creates a scaler whose output has the display dimensions of the decoded stream.
+    pub(crate) fn new(decoder: &ffmpeg::decoder::Video) -> Self {
+        let (width, height) =
+            display_dimensions(decoder.width(), decoder.height(), decoder.aspect_ratio());
+        Self {
+            stream_transfer: decoder.color_transfer_characteristic(),
+            ..Self::with_size(width, height)
+        }
+    }
+
+    pub(crate) fn with_size(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            flags: scaling::Flags::BICUBIC | scaling::Flags::ACCURATE_RND,
+            tone_map: ToneMap::default(),
+            stream_transfer: color::TransferCharacteristic::Unspecified,
+            context: None,
+            tone_mapper: None,
+        }
+    }
+
+    pub(crate) fn with_tone_map(mut self, tone_map: ToneMap) -> Self {
+        self.tone_map = tone_map;
+        self
+    }
+
+    /// Scales the output to fit in a `size` x `size` tile, keeping the aspect
+    /// ratio, so small tiles are not converted at full resolution first.
+    pub(crate) fn fit_in(mut self, size: u32) -> Self {
+        (self.width, self.height) = fit_dimensions(self.width, self.height, size);
+        self.flags = scaling::Flags::LANCZOS | scaling::Flags::ACCURATE_RND;
+        self
+    }
+
+    pub(crate) fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub(crate) fn run(&mut self, decoded: &frame::Video) -> anyhow::Result<RgbaImage> {
+        let transfer = match decoded.color_transfer_characteristic() {
+            color::TransferCharacteristic::Unspecified => self.stream_transfer,
+            transfer => transfer,
+        };
+        let hdr_transfer = match self.tone_map {
+            ToneMap::Off => None,
+            _ => HdrTransfer::from_characteristic(transfer),
+        };
+        let source = Source {
+            format: decoded.format(),
+            width: decoded.width(),
+            height: decoded.height(),
+            color_space: decoded.color_space(),
+            color_range: decoded.color_range(),
+            hdr_transfer,
+        };
+
+        // the swscontext is (re)created from the frame itself, as the stream
+        // parameters may be missing or change in the middle of the file
+        let (_, context) = match self.context.take() {
+            Some((current, context)) if current == source => {
+                self.context.insert((current, context))
+            }
+            _ => {
+                let mut context = scaling::Context::get(
+                    source.format,
+                    source.width,
+                    source.height,
+                    if source.hdr_transfer.is_some() {
+                        format::Pixel::RGBA64LE
+                    } else {
+                        format::Pixel::RGBA
+                    },
+                    self.width,
+                    self.height,
+                    self.flags,
+                )
+                .context("invalid swscontext parameter")?;
+                set_colorspace_details(&mut context, &source);
+                self.context.insert((source, context))
+            }
+        };
+
+        let mut rgb_frame = frame::Video::empty();
+        context
+            .run(decoded, &mut rgb_frame)
+            .context("error swscontext run")?;
+
+        let hdr_transfer = match source.hdr_transfer {
+            Some(hdr_transfer) => hdr_transfer,
+            None => return Ok(frame_to_image(&rgb_frame)),
+        };
+
+        let tone_mapper = match self.tone_mapper.take() {
+            Some(tone_mapper) if tone_mapper.transfer() == hdr_transfer => {
+                self.tone_mapper.insert(tone_mapper)
+            }
+            _ => self
+                .tone_mapper
+                .insert(ToneMapper::new(self.tone_map, hdr_transfer)),
+        };
+
+        Ok(tone_mapper.run(&rgb_frame))
+    }
+}
+
+/// Frame dimensions once the sample aspect ratio is applied. Only the width
+/// is stretched, as reference players do, and it is kept even.
+pub(crate) fn display_dimensions(
+    width: u32,
+    height: u32,
+    aspect_ratio: ffmpeg::Rational,
+) -> (u32, u32) {
+    if aspect_ratio.numerator() <= 0
+        || aspect_ratio.denominator() <= 0
+        || aspect_ratio.numerator() == aspect_ratio.denominator()
+    {
+        return (width, height);
+    }
+
+    let display_width = (width as f64 * f64::from(aspect_ratio)).round() as u32;
+    ((display_width + 1) & !1, height)
+}
+
+/// Largest dimensions with the same aspect ratio that fit in a `size` x `size` box.
+pub(crate) fn fit_dimensions(width: u32, height: u32, size: u32) -> (u32, u32) {
+    if width == 0 || height == 0 {
+        return (width, height);
+    }
+
+    let ratio = (size as f64 / width as f64).min(size as f64 / height as f64);
+    let fit = |value: u32| ((value as f64 * ratio).round() as u32).max(1);
+    (fit(width), fit(height))
+}
+
+fn set_colorspace_details(context: &mut scaling::Context, source: &Source) {
+    let is_rgb = source.format.descriptor().is_some_and(|descriptor| unsafe {
+        (*descriptor.as_ptr()).flags & u64::from(ffmpeg::ffi::AV_PIX_FMT_FLAG_RGB) != 0
+    });
+    if is_rgb {
+        return;
+    }
+
+    // streams without colour metadata follow the usual player heuristic:
+    // BT.709 for HD and BT.601 for SD material
+    let color_space = match source.color_space {
+        color::Space::Unspecified | color::Space::Reserved => {
+            if source.height >= 720 {
+                color::Space::BT709
+            } else {
+                color::Space::BT470BG
+            }
+        }
+        color_space => color_space,
+    };
+    let is_full_range = source.color_range == color::Range::JPEG
+        || matches!(
+            source.format,
+            format::Pixel::YUVJ420P
+                | format::Pixel::YUVJ422P
+                | format::Pixel::YUVJ440P
+                | format::Pixel::YUVJ444P
+        );
+    let src_range = i32::from(is_full_range);
+
+    unsafe {
+        let coefficients =
+            ffmpeg::ffi::sws_getCoefficients(ffmpeg::ffi::AVColorSpace::from(color_space) as i32);
+        ffmpeg::ffi::sws_setColorspaceDetails(
+            context.as_mut_ptr(),
+            coefficients,
+            src_range,
+            ffmpeg::ffi::sws_getCoefficients(ffmpeg::ffi::SWS_CS_DEFAULT as i32),
+            1,
+            0,
+            1 << 16,
+            1 << 16,
+        );
+    }
+}
+
+/// Copies an RGBA frame into an image buffer, dropping the row padding.
+pub(crate) fn frame_to_image(rgb_frame: &frame::Video) -> RgbaImage {
+    let width = rgb_frame.width();
+    let height = rgb_frame.height();
+    let stride = rgb_frame.stride(0);
+    let row_size = width as usize * 4;
+
+    let mut data = Vec::with_capacity(row_size * height as usize);
+    for row in rgb_frame.data(0).chunks(stride).take(height as usize) {
+        data.extend_from_slice(&row[..row_size]);
+    }
+
+    RgbaImage::from_raw(width, height, data).unwrap_or_else(|| RgbaImage::new(width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_dimensions() {
+        // anamorphic PAL DVD (16:9)
+        let dimensions = display_dimensions(720, 576, ffmpeg::Rational::new(64, 45));
+        assert_eq!(dimensions, (1024, 576));
+
+        // square pixels
+        let dimensions = display_dimensions(1920, 1080, ffmpeg::Rational::new(1, 1));
+        assert_eq!(dimensions, (1920, 1080));
+
+        // unknown aspect ratio
+        let dimensions = display_dimensions(640, 480, ffmpeg::Rational::new(0, 1));
+        assert_eq!(dimensions, (640, 480));
+    }
+
+    #[test]
+    fn test_fit_dimensions() {
+        assert_eq!(fit_dimensions(1920, 1080, 300), (300, 169));
+        assert_eq!(fit_dimensions(1080, 1920, 300), (169, 300));
+        assert_eq!(fit_dimensions(160, 120, 300), (300, 225));
+    }
+}
@@ -1,9 +1,16 @@
 use anyhow;
-use ffmpeg_next::{self as ffmpeg};
 use std::path::Path;
 
+use super::{
+    input::{self, InputOptions},
+    metadata::{self, MediaInfo},
+    sequence::ImageSequence,
+};
+
+/// Extensions of containers, DVR formats and raw elementary streams.
 const MEDIA_TYPE_VIDEOS: &[&str] = &[
-    "mpeg", "mpg", "mp4", "avi", "ogg", "webm", "flv", "mov", "mkv",
+    "mpeg", "mpg", "mp4", "avi", "ogg", "webm", "flv", "mov", "mkv", "ts", "m2ts", "mts", "vob",
+    "3gp", "wmv", "asf", "mjpeg", "mjpg", "dav", "h264", "264", "h265", "265", "hevc", "m4v",
 ];
 
 #[derive(Debug, Clone, Default)]
@@ -16,9 +23,32 @@ pub struct Video {
     pub nb_frames: i64,
     pub width: u32,
     pub height: u32,
+    pub metadata: MediaInfo,
 }
 
-pub async fn search_videos<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<Video>> {
+/// Lists the videos of a folder. A URL gives a single video, read from the
+/// network.
+pub async fn search_videos<P: AsRef<Path>>(
+    path: P,
+    input: InputOptions,
+) -> anyhow::Result<Vec<Video>> {
+    if input::is_url(&path) {
+        let url = path.as_ref().to_string_lossy().to_string();
+        // opening a stream blocks until the server answers or times out
+        let media_info = {
+            let url = url.clone();
+            tokio::task::spawn_blocking(move || metadata::get_media_info_with_input(&url, &input))
+                .await??
+        };
+        let mut video = Video {
+            name: input::media_name(&url),
+            path: url,
+            ..Default::default()
+        };
+        video.set_media_info(media_info);
+        return Ok(vec![video]);
+    }
+
     let mut videos = Vec::new();
     let mut dir = tokio::fs::read_dir(path).await?;
 
@@ -26,25 +56,30 @@ pub async fn search_videos<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<Video>
         let path = entry.path();
         let metadata = tokio::fs::metadata(&path).await?;
 
-        if metadata.is_dir() || !is_video(&path) {
+        // folders of numbered images are opened as one video
+        let size = if metadata.is_dir() {
+            match ImageSequence::detect(&path, input.frame_rate) {
+                Some(sequence) => sequence.size(),
+                None => continue,
+            }
+        } else if is_video(&path) {
+            metadata.len()
+        } else {
             continue;
-        }
+        };
 
         let video_path = entry.path().to_str().unwrap().to_owned();
 
         let mut video = Video {
             name: entry.file_name().to_str().unwrap().to_owned(),
             path: video_path.clone(),
-            size: (metadata.len() as f64 / 1024.0_f64).round() as usize,
+            size: (size as f64 / 1024.0_f64).round() as usize,
             ..Default::default()
         };
 
-        if let Ok(metada) = get_metadata(&video_path) {
-            video.duration = metada.duration;
-            video.rate = metada.rate;
-            video.nb_frames = metada.nb_frames;
-            video.width = metada.width;
-            video.height = metada.height;
+        let input = input.for_path(&video_path);
+        if let Ok(media_info) = metadata::get_media_info_with_input(&video_path, &input) {
+            video.set_media_info(media_info);
         }
 
         videos.push(video);
@@ -53,55 +88,29 @@ pub async fn search_videos<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<Video>
     Ok(videos)
 }
 
+impl Video {
+    fn set_media_info(&mut self, media_info: MediaInfo) {
+        self.duration = media_info.duration;
+        if let Some(video_stream) = media_info.video_stream() {
+            self.rate = video_stream.frame_rate;
+            self.nb_frames = if video_stream.nb_frames > 0 {
+                video_stream.nb_frames
+            } else {
+                (media_info.duration * video_stream.frame_rate) as i64
+            };
+            self.width = video_stream.width;
+            self.height = video_stream.height;
+        }
+        self.metadata = media_info;
+    }
+}
+
 fn is_video(entry: &Path) -> bool {
     matches!(
         entry.extension(), 
         Some(ext) if MEDIA_TYPE_VIDEOS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
 }
 
-#[derive(Default)]
-struct Metadata {
-    duration: f64,
-    rate: f64,
-    nb_frames: i64,
-    width: u32,
-    height: u32,
-}
-
-fn get_metadata(video_path: &str) -> anyhow::Result<Metadata> {
-    ffmpeg::init()?;
-    let context = ffmpeg::format::input(video_path)?;
-
-    let mut metadata = Metadata {
-        // duration (seconds)
-        duration: context.duration() as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE),
-        ..Default::default()
-    };
-
-    if let Some(video_stream) = context.streams().best(ffmpeg::media::Type::Video) {
-        metadata.rate = if video_stream.rate().denominator() > 0 {
-            video_stream.rate().numerator() as f64 / video_stream.rate().denominator() as f64
-        } else {
-            0f64
-        };
-        metadata.nb_frames = if video_stream.frames() > 0 {
-            video_stream.frames()
-        } else {
-            (metadata.duration * metadata.rate) as i64
-        };
-
-        let codec = ffmpeg::codec::context::Context::from_parameters(video_stream.parameters())?;
-        if codec.medium() == ffmpeg::media::Type::Video {
-            if let Ok(video) = codec.decoder().video() {
-                metadata.width = video.width();
-                metadata.height = video.height();
-            }
-        }
-    }
-
-    Ok(metadata)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,7 +118,9 @@ mod tests {
     #[tokio::test]
     async fn test_search_videos() {
         let path = Path::new("../data/video");
-        let videos = search_videos(path).await.expect("Failed to search videos.");
+        let videos = search_videos(path, InputOptions::default())
+            .await
+            .expect("Failed to search videos.");
 
         println!("{:#?}", videos);
 
@@ -0,0 +1,275 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::{self, Context};
+use ffmpeg::format;
+use ffmpeg_next::{self as ffmpeg};
+
+use super::input::{self, InputOptions};
+
+/// Folders with fewer images are not taken as a video.
+const MIN_SEQUENCE_IMAGES: usize = 10;
+/// Frame rate used when neither the options nor the file names give one.
+const DEFAULT_SEQUENCE_RATE: f64 = 25.0;
+const SEQUENCE_IMAGE_TYPES: &[&str] = &["jpg", "jpeg", "png", "bmp"];
+/// Numbers the concat scripts, so concurrent opens use their own file.
+static SCRIPT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A folder of numbered images, opened as a video.
+#[derive(Debug)]
+pub(crate) struct ImageSequence {
+    /// Images in playback order with their time in seconds.
+    pub(crate) images: Vec<(PathBuf, f64)>,
+    /// Duration of the last image.
+    last_duration: f64,
+}
+
+impl ImageSequence {
+    /// Lists the images of `dir`. Their times come from the file names when
+    /// every name holds a date and time, otherwise from the frame rate.
+    pub(crate) fn detect<P: AsRef<Path>>(dir: P, frame_rate: Option<f64>) -> Option<Self> {
+        let mut by_type: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for entry in fs::read_dir(&dir).ok()?.flatten() {
+            let path = entry.path();
+            let extension = path
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase());
+            match extension {
+                Some(ext) if SEQUENCE_IMAGE_TYPES.contains(&ext.as_str()) && path.is_file() => {
+                    by_type.entry(ext).or_default().push(path);
+                }
+                _ => {}
+            }
+        }
+
+        // every image of a sequence must use the same codec
+        let images = by_type.into_values().max_by_key(|images| images.len())?;
+        if images.len() < MIN_SEQUENCE_IMAGES {
+            return None;
+        }
+
+        let frame_duration = 1.0
+            / frame_rate
+                .filter(|rate| *rate > 0.0)
+                .unwrap_or(DEFAULT_SEQUENCE_RATE);
+        let timestamps: Option<Vec<f64>> = images
+            .iter()
+            .map(|path| parse_timestamp(&path.file_stem()?.to_string_lossy()))
+            .collect();
+
+        let images = match timestamps {
+            Some(timestamps) => {
+                let mut images: Vec<(PathBuf, f64)> = images.into_iter().zip(timestamps).collect();
+                images.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+                spread_equal_times(&mut images);
+                let start = images[0].1;
+                images.iter_mut().for_each(|(_, time)| *time -= start);
+                images
+            }
+            None => {
+                let mut images = images;
+                images.sort_by_key(|path| {
+                    let name = path
+                        .file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string();
+                    (last_number(&name), name)
+                });
+                images
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, path)| (path, i as f64 * frame_duration))
+                    .collect()
+            }
+        };
+
+        let last_duration = match images.as_slice() {
+            [.., (_, previous), (_, last)] if last > previous => last - previous,
+            _ => frame_duration,
+        };
+
+        Some(Self {
+            images,
+            last_duration,
+        })
+    }
+
+    /// Total size of the images in bytes.
+    pub(crate) fn size(&self) -> u64 {
+        self.images
+            .iter()
+            .filter_map(|(path, _)| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+
+    /// Script for the FFmpeg concat demuxer, which gives every image its time.
+    fn ffconcat_script(&self) -> String {
+        let mut script = String::from("ffconcat version 1.0\n");
+        for (i, (path, time)) in self.images.iter().enumerate() {
+            let duration = match self.images.get(i + 1) {
+                Some((_, next)) => next - time,
+                None => self.last_duration,
+            };
+            let path = path.to_string_lossy().replace('\'', "'\\''");
+            script.push_str(&format!("file '{}'\nduration {:.6}\n", path, duration));
+        }
+        script
+    }
+}
+
+/// Opens a folder of images through a generated concat script. The script is
+/// read whole while opening, so it is removed right after.
+pub(crate) fn open_sequence<P: AsRef<Path>>(
+    dir: P,
+    input: &InputOptions,
+) -> anyhow::Result<format::context::Input> {
+    let sequence = ImageSequence::detect(&dir, input.frame_rate)
+        .with_context(|| format!("{} is not an image sequence", dir.as_ref().display()))?;
+
+    let script_path = std::env::temp_dir().join(format!(
+        "vimpeg-sequence-{}-{}.ffconcat",
+        process::id(),
+        SCRIPT_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&script_path, sequence.ffconcat_script())
+        .with_context(|| format!("failed to write {}", script_path.display()))?;
+
+    let mut dictionary = ffmpeg::Dictionary::new();
+    // the script holds absolute paths
+    dictionary.set("safe", "0");
+    let input_format_context = input::open_with_demuxer(&script_path, "concat", dictionary);
+    let _ = fs::remove_file(&script_path);
+    input_format_context
+}
+
+/// Images taken in the same second, when the names have no milliseconds,
+/// are spread evenly over that second.
+fn spread_equal_times(images: &mut [(PathBuf, f64)]) {
+    let mut start = 0;
+    while start < images.len() {
+        let time = images[start].1;
+        let end = images[start..]
+            .iter()
+            .position(|(_, other)| *other != time)
+            .map_or(images.len(), |count| start + count);
+        let count = end - start;
+        for (i, (_, image_time)) in images[start..end].iter_mut().enumerate() {
+            *image_time = time + i as f64 / count as f64;
+        }
+        start = end;
+    }
+}
+
+fn last_number(name: &str) -> u64 {
+    name.split(|c: char| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .last()
+        .and_then(|run| run.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Seconds since the epoch of the date and time in a file name, such as
+/// `cam1_20240131_235959_250` or `2024-01-31 23-59-59`.
+pub(crate) fn parse_timestamp(name: &str) -> Option<f64> {
+    let runs: Vec<&str> = name
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .collect();
+
+    for start in 0..runs.len() {
+        let mut digits = String::new();
+        let mut end = start;
+        while digits.len() < 14 && end < runs.len() {
+            digits.push_str(runs[end]);
+            end += 1;
+        }
+        if digits.len() < 14 {
+            break;
+        }
+
+        if let Some(seconds) = parse_date_time(&digits[..14]) {
+            // milliseconds follow the seconds, in the same run or the next one
+            let fraction = match &digits[14..] {
+                "" => runs.get(end).copied().filter(|run| run.len() <= 3),
+                rest => Some(rest).filter(|rest| rest.len() <= 3),
+            };
+            let fraction = fraction.map_or(0.0, |fraction| {
+                fraction.parse::<f64>().unwrap_or(0.0) / 10f64.powi(fraction.len() as i32)
+            });
+            return Some(seconds + fraction);
+        }
+    }
+
+    None
+}
+
+fn parse_date_time(digits: &str) -> Option<f64> {
+    let field = |range: std::ops::Range<usize>| digits.get(range)?.parse::<i64>().ok();
+    let (year, month, day) = (field(0..4)?, field(4..6)?, field(6..8)?);
+    let (hour, minute, second) = (field(8..10)?, field(10..12)?, field(12..14)?);
+
+    let is_valid = (1970..=2100).contains(&year)
+        && (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && hour < 24
+        && minute < 60
+        && second < 60;
+    if !is_valid {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    Some((days * 86400 + hour * 3600 + minute * 60 + second) as f64)
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        let time = parse_timestamp("cam1_20240131_235959_250").unwrap();
+        let next = parse_timestamp("cam1_2024-02-01 00-00-01").unwrap();
+
+        // Assert
+        assert_eq!(parse_timestamp("19700101000000"), Some(0.0));
+        assert!((next - time - 1.75).abs() < 1e-6);
+        assert_eq!(parse_timestamp("img_00042"), None);
+    }
+
+    #[test]
+    fn test_ffconcat_script() {
+        let mut images = vec![
+            (PathBuf::from("/tmp/a.jpg"), 0.0),
+            (PathBuf::from("/tmp/it's.jpg"), 0.0),
+            (PathBuf::from("/tmp/c.jpg"), 1.0),
+        ];
+        spread_equal_times(&mut images);
+        let sequence = ImageSequence {
+            images,
+            last_duration: 0.5,
+        };
+        let script = sequence.ffconcat_script();
+
+        // Assert
+        assert!(script.contains("file '/tmp/it'\\''s.jpg'\nduration 0.500000\n"));
+        assert!(script.ends_with("file '/tmp/c.jpg'\nduration 0.500000\n"));
+    }
+}
@@ -1,17 +1,43 @@
-use std::path::Path;
+use std::{
+    path::Path,
+    sync::{atomic::AtomicBool, Arc},
+};
 
-use super::{decoder, search};
+use super::{
+    clip, decoder, heatmap, preview, search, slitscan, sprite, stack, timelapse, Deinterlace,
+    InputOptions,
+};
 
-pub async fn search_videos<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<search::Video>> {
-    search::search_videos(path).await
+pub async fn search_videos<P: AsRef<Path>>(
+    path: P,
+    input: InputOptions,
+) -> anyhow::Result<Vec<search::Video>> {
+    search::search_videos(path, input).await
 }
 
+pub use clip::{ClipOptions, ClipResult};
+pub use decoder::{
+    CaptureEvent, CaptureOptions, CaptureResult, DecodeError, DecodeOptions, DecodeReport,
+    THUMBNAIL_SAMPLE_POSITION,
+};
+pub use preview::{PreviewFormat, PreviewOptions, PreviewResult};
+pub use slitscan::{SlitOrientation, SlitScanOptions, SlitScanResult};
+pub use sprite::{SpriteOptions, SpriteResult};
+pub use stack::{StackMode, StackOptions, StackResult};
+pub use timelapse::{TimelapseOptions, TimelapseResult};
+
 pub async fn get_video_thumbnail<P: AsRef<Path>>(
     video_path: P,
+    sample_position: f64,
+    deinterlace: Deinterlace,
+    input: InputOptions,
 ) -> anyhow::Result<decoder::VideoThumb> {
     let video_path = video_path.as_ref().to_owned();
 
-    tokio::task::spawn_blocking(move || decoder::get_thumbnail(video_path)).await?
+    tokio::task::spawn_blocking(move || {
+        decoder::get_thumbnail(video_path, sample_position, deinterlace, input)
+    })
+    .await?
 }
 
 pub async fn dump_video_frames_into_image<P: AsRef<Path>>(
@@ -20,12 +46,46 @@ pub async fn dump_video_frames_into_image<P: AsRef<Path>>(
     cols: usize,
     rows: usize,
     show_timestamp: bool,
-) -> anyhow::Result<()> {
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
     let video_path = video_path.as_ref().to_owned();
     let image_path = image_path.as_ref().to_owned();
 
     tokio::task::spawn_blocking(move || {
-        decoder::dump_video_frames_into_image(video_path, image_path, cols, rows, show_timestamp)
+        decoder::dump_video_frames_into_image(
+            video_path,
+            image_path,
+            cols,
+            rows,
+            show_timestamp,
+            options,
+        )
+    })
+    .await?
+}
+
+pub async fn dump_tracks_into_image<P: AsRef<Path>>(
+    video_path: P,
+    image_path: P,
+    cols: usize,
+    rows: usize,
+    tracks: Vec<usize>,
+    show_timestamp: bool,
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
+    let video_path = video_path.as_ref().to_owned();
+    let image_path = image_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        decoder::dump_tracks_into_image(
+            video_path,
+            image_path,
+            cols,
+            rows,
+            &tracks,
+            show_timestamp,
+            options,
+        )
     })
     .await?
 }
@@ -37,6 +97,7 @@ pub async fn dump_video_frames_by_time<P: AsRef<Path>>(
     time_end: f64,
     frame_rate: u32,
     show_timestamp: bool,
+    options: DecodeOptions,
 ) -> anyhow::Result<decoder::VideoDumpResult> {
     let video_path = video_path.as_ref().to_owned();
     let save_path = save_path.as_ref().to_owned();
@@ -49,7 +110,172 @@ pub async fn dump_video_frames_by_time<P: AsRef<Path>>(
             time_end,
             frame_rate,
             show_timestamp,
+            options,
+        )
+    })
+    .await?
+}
+
+pub async fn dump_video_preview<P: AsRef<Path>>(
+    video_path: P,
+    preview_path: P,
+    nframes: usize,
+    show_timestamp: bool,
+    preview: PreviewOptions,
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
+    let video_path = video_path.as_ref().to_owned();
+    let preview_path = preview_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        preview::dump_video_preview(
+            video_path,
+            preview_path,
+            nframes,
+            show_timestamp,
+            preview,
+            options,
+        )
+    })
+    .await?
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn dump_video_preview_by_time<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    time_start: f64,
+    time_end: f64,
+    frame_rate: u32,
+    show_timestamp: bool,
+    preview: PreviewOptions,
+    options: DecodeOptions,
+) -> anyhow::Result<PreviewResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        preview::dump_video_preview_by_time(
+            video_path,
+            save_path,
+            time_start,
+            time_end,
+            frame_rate,
+            show_timestamp,
+            preview,
+            options,
+        )
+    })
+    .await?
+}
+
+pub async fn dump_video_sprites<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    show_timestamp: bool,
+    sprite: SpriteOptions,
+    options: DecodeOptions,
+) -> anyhow::Result<SpriteResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        sprite::dump_video_sprites(video_path, save_path, show_timestamp, sprite, options)
+    })
+    .await?
+}
+
+pub async fn dump_motion_heatmap<P: AsRef<Path>>(
+    video_path: P,
+    image_path: P,
+    interval: u32,
+    options: DecodeOptions,
+) -> anyhow::Result<DecodeReport> {
+    let video_path = video_path.as_ref().to_owned();
+    let image_path = image_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        heatmap::dump_motion_heatmap(video_path, image_path, interval, options)
+    })
+    .await?
+}
+
+/// Runs a periodic snapshot capture until `stop` is set.
+pub async fn capture_snapshots<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    options: CaptureOptions,
+    stop: Arc<AtomicBool>,
+    on_event: impl FnMut(CaptureEvent) + Send + 'static,
+) -> anyhow::Result<CaptureResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        decoder::capture_snapshots(video_path, save_path, options, &stop, on_event)
+    })
+    .await?
+}
+
+/// Runs a time-lapse export until the end of the file or until `stop` is set.
+pub async fn export_timelapse<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    options: TimelapseOptions,
+    stop: Arc<AtomicBool>,
+) -> anyhow::Result<TimelapseResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        timelapse::export_timelapse(video_path, save_path, options, &stop)
+    })
+    .await?
+}
+
+pub async fn export_clip<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    time_start: f64,
+    time_end: f64,
+    options: ClipOptions,
+) -> anyhow::Result<ClipResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        clip::export_clip(video_path, save_path, time_start, time_end, options)
+    })
+    .await?
+}
+
+pub async fn stack_frames<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    time_start: f64,
+    time_end: f64,
+    frame_rate: u32,
+    options: StackOptions,
+) -> anyhow::Result<StackResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || {
+        stack::stack_frames(
+            video_path, save_path, time_start, time_end, frame_rate, options,
         )
     })
     .await?
 }
+
+pub async fn dump_slit_scan<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    options: SlitScanOptions,
+) -> anyhow::Result<SlitScanResult> {
+    let video_path = video_path.as_ref().to_owned();
+    let save_path = save_path.as_ref().to_owned();
+
+    tokio::task::spawn_blocking(move || slitscan::dump_slit_scan(video_path, save_path, options))
+        .await?
+}
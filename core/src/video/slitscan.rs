@@ -0,0 +1,266 @@
+use std::{path::Path, sync::atomic::AtomicBool};
+
+use anyhow::{self, Context};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use super::decoder::{self, DecodeOptions, DecodeReport};
+
+/// Samples per image, longer recordings are split over several images.
+const MAX_STRIP_SAMPLES: usize = 3600;
+/// Height of the time axis drawn under the strip.
+const AXIS_HEIGHT: u32 = 28;
+const TICK_HEIGHT: u32 = 6;
+/// Height in pixels of the tick labels, which fit under the ticks.
+const LABEL_FONT_HEIGHT: f32 = 10.0;
+const AXIS_BACKGROUND: Rgba<u8> = Rgba([32, 32, 32, 255]);
+const TICK_COLOR: Rgba<u8> = Rgba([255, 111, 0, 255]);
+/// Seconds between two ticks, the first one leaving enough room for a label
+/// is used.
+const TICK_INTERVALS: &[u32] = &[1, 5, 10, 30, 60, 300, 600, 1800, 3600];
+const MIN_TICK_SPACING: u32 = 120;
+
+/// Line of the frame taken from every sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SlitOrientation {
+    /// A pixel column, for movement across the frame.
+    #[default]
+    Vertical,
+    /// A pixel row, for movement up or down the frame.
+    Horizontal,
+}
+
+/// Settings of a slit-scan.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlitScanOptions {
+    pub orientation: SlitOrientation,
+    /// Place of the slit, from 0 at the left or top to 1 at the right or
+    /// bottom of the frame.
+    pub position: f64,
+    /// Samples taken per second of the recording, one pixel of the strip each.
+    pub frame_rate: u32,
+    pub decode: DecodeOptions,
+}
+
+#[derive(Debug)]
+pub struct SlitScanResult {
+    pub file_name: String,
+    pub image_paths: Vec<String>,
+    pub samples: usize,
+    pub report: DecodeReport,
+}
+
+/// Stacks the slit of every sample side by side, time running from left to
+/// right, in a single pass over the video.
+pub fn dump_slit_scan<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    options: SlitScanOptions,
+) -> anyhow::Result<SlitScanResult> {
+    if options.frame_rate == 0 {
+        anyhow::bail!("frame rate must be greater than zero");
+    }
+
+    let (video_name, save_path) =
+        decoder::output_dir(&video_path, save_path.as_ref(), options.decode.video_track)?;
+
+    let tick = tick_interval(options.frame_rate);
+    let font_settings = decoder::get_font_settings_with_height(LABEL_FONT_HEIGHT)?;
+    let mut strip = Strip::default();
+    let mut samples = 0;
+    let mut image_paths = Vec::new();
+
+    let mut save_strip = |strip: &Strip| -> anyhow::Result<()> {
+        let image_path =
+            save_path.join(format!("{}-slitscan-{}.png", video_name, image_paths.len()));
+        strip
+            .render(tick, &font_settings)
+            .save(&image_path)
+            .with_context(|| format!("failed to save image {}", image_path.display()))?;
+        image_paths.push(image_path.to_string_lossy().to_string());
+        Ok(())
+    };
+
+    let report = decoder::sample_frames_by_step(
+        &video_path,
+        0.0,
+        f64::INFINITY,
+        1.0 / f64::from(options.frame_rate),
+        None,
+        options.decode,
+        &AtomicBool::new(false),
+        |img, timestamp, _| {
+            let slit = read_slit(&img, options.orientation, options.position);
+            if strip.push(slit, timestamp) {
+                samples += 1;
+            }
+            if strip.timestamps.len() == MAX_STRIP_SAMPLES {
+                save_strip(&strip)?;
+                strip = Strip::default();
+            }
+            Ok(())
+        },
+    )?;
+
+    if !strip.timestamps.is_empty() {
+        save_strip(&strip)?;
+    }
+    if samples == 0 {
+        anyhow::bail!("could not decode any video frame");
+    }
+
+    Ok(SlitScanResult {
+        file_name: video_name,
+        image_paths,
+        samples,
+        report,
+    })
+}
+
+/// RGB values of the slit, from the left or top of the frame.
+fn read_slit(img: &RgbaImage, orientation: SlitOrientation, position: f64) -> Vec<u8> {
+    let (width, height) = img.dimensions();
+    let place = |size: u32| (position.clamp(0.0, 1.0) * f64::from(size - 1)).round() as u32;
+
+    let pixels: Vec<&Rgba<u8>> = match orientation {
+        SlitOrientation::Vertical => {
+            let x = place(width);
+            (0..height).map(|y| img.get_pixel(x, y)).collect()
+        }
+        SlitOrientation::Horizontal => {
+            let y = place(height);
+            (0..width).map(|x| img.get_pixel(x, y)).collect()
+        }
+    };
+    pixels
+        .iter()
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect()
+}
+
+/// Slits gathered for one image, a column each.
+#[derive(Debug, Default)]
+struct Strip {
+    /// RGB values of every slit, one after the other.
+    slits: Vec<u8>,
+    timestamps: Vec<f64>,
+}
+
+impl Strip {
+    /// Adds a slit, unless its length differs from the first one after a
+    /// resolution change.
+    fn push(&mut self, slit: Vec<u8>, timestamp: f64) -> bool {
+        if !self.timestamps.is_empty() && slit.len() != self.slit_length() * 3 {
+            return false;
+        }
+        self.slits.extend(slit);
+        self.timestamps.push(timestamp);
+        true
+    }
+
+    fn slit_length(&self) -> usize {
+        self.slits.len() / 3 / self.timestamps.len().max(1)
+    }
+
+    fn render(&self, tick: u32, font_settings: &decoder::FontSettings) -> RgbaImage {
+        let width = self.timestamps.len() as u32;
+        let length = self.slit_length() as u32;
+        let mut img = RgbaImage::from_pixel(width, length + AXIS_HEIGHT, AXIS_BACKGROUND);
+
+        for (x, slit) in self
+            .slits
+            .chunks_exact(length.max(1) as usize * 3)
+            .enumerate()
+        {
+            for (y, rgb) in slit.chunks_exact(3).enumerate() {
+                img.put_pixel(x as u32, y as u32, Rgba([rgb[0], rgb[1], rgb[2], 255]));
+            }
+        }
+
+        let ticks = tick_positions(&self.timestamps, tick);
+        for &(x, _) in &ticks {
+            for y in length..length + TICK_HEIGHT {
+                img.put_pixel(x, y, TICK_COLOR);
+            }
+        }
+
+        let mut img = DynamicImage::ImageRgba8(img);
+        for (x, timestamp) in ticks {
+            decoder::draw_text(
+                &mut img,
+                &tick_label(timestamp),
+                x as i32 + 3,
+                (length + TICK_HEIGHT + 2) as i32,
+                font_settings,
+            );
+        }
+        img.into_rgba8()
+    }
+}
+
+/// Seconds between two ticks so that labels do not overlap.
+fn tick_interval(frame_rate: u32) -> u32 {
+    TICK_INTERVALS
+        .iter()
+        .copied()
+        .find(|&tick| tick * frame_rate >= MIN_TICK_SPACING)
+        .unwrap_or(TICK_INTERVALS[TICK_INTERVALS.len() - 1])
+}
+
+/// Columns where the time crosses a multiple of `tick`, with that time. The
+/// first column is marked with its own time, unless the next tick is too
+/// close for both labels.
+fn tick_positions(timestamps: &[f64], tick: u32) -> Vec<(u32, f64)> {
+    let tick = f64::from(tick);
+    let mut ticks = Vec::new();
+    let mut previous: Option<f64> = None;
+
+    for (x, &timestamp) in timestamps.iter().enumerate() {
+        let step = (timestamp / tick).floor();
+        match previous {
+            None => ticks.push((x as u32, timestamp)),
+            Some(previous) if step > previous => ticks.push((x as u32, step * tick)),
+            Some(_) => {}
+        }
+        previous = Some(step);
+    }
+    let is_crowded = matches!(
+        ticks.as_slice(),
+        [(first, _), (second, _), ..] if second - first < MIN_TICK_SPACING
+    );
+    if is_crowded {
+        ticks.remove(0);
+    }
+    ticks
+}
+
+fn tick_label(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_positions() {
+        let timestamps: Vec<f64> = (0..25).map(|i| 55.5 + f64::from(i) * 0.5).collect();
+        let ticks = tick_positions(&timestamps, tick_interval(25));
+        // at 1 sample per second the first tick is far enough from the next
+        let timestamps: Vec<f64> = (0..600).map(|i| 100.0 + f64::from(i)).collect();
+        let sparse_ticks = tick_positions(&timestamps, tick_interval(1));
+
+        // Assert
+        assert_eq!(tick_interval(25), 5);
+        assert_eq!(tick_interval(1), 300);
+        // the first column is too close to the tick at 60 s to be labelled
+        assert_eq!(ticks, vec![(9, 60.0), (19, 65.0)]);
+        assert_eq!(sparse_ticks, vec![(0, 100.0), (200, 300.0), (500, 600.0)]);
+        assert_eq!(tick_label(3725.0), "01:02:05");
+    }
+}
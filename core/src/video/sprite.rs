@@ -0,0 +1,252 @@
+use std::{fmt::Write, fs, path::Path, sync::atomic::AtomicBool};
+
+use anyhow::{self, Context};
+use image::{imageops::FilterType, DynamicImage};
+use serde::{Deserialize, Serialize};
+
+use super::decoder::{self, DecodeOptions, DecodeReport};
+
+/// Seconds between two thumbnails.
+const DEFAULT_SPRITE_INTERVAL: u32 = 10;
+/// Largest width and height of a thumbnail.
+const DEFAULT_SPRITE_TILE_SIZE: u32 = 160;
+/// Thumbnails per row and rows per sprite sheet.
+const DEFAULT_SPRITE_COLUMNS: usize = 10;
+const DEFAULT_SPRITE_ROWS: usize = 10;
+
+/// Settings of the seek-preview thumbnails of a video.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpriteOptions {
+    /// Seconds between two thumbnails.
+    pub interval: u32,
+    /// Largest width and height of a thumbnail.
+    pub tile_size: u32,
+    /// Thumbnails per row of a sprite sheet.
+    pub columns: usize,
+    /// Rows of a sprite sheet, a new sheet is started once it is full.
+    pub rows: usize,
+}
+
+impl Default for SpriteOptions {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_SPRITE_INTERVAL,
+            tile_size: DEFAULT_SPRITE_TILE_SIZE,
+            columns: DEFAULT_SPRITE_COLUMNS,
+            rows: DEFAULT_SPRITE_ROWS,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SpriteResult {
+    pub file_name: String,
+    pub sheet_paths: Vec<String>,
+    pub vtt_path: String,
+    pub report: DecodeReport,
+}
+
+/// Region of a sprite sheet shown from `start` to `end`.
+#[derive(Debug, PartialEq)]
+struct SpriteCue {
+    start: f64,
+    end: f64,
+    sheet: usize,
+    x: u32,
+    y: u32,
+}
+
+/// Saves a thumbnail every `interval` seconds of the video into tiled sprite
+/// sheets, with a WebVTT track mapping every time range to its region. Every
+/// sheet is saved once it is full, so only its tiles are held in memory.
+pub fn dump_video_sprites<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    show_timestamp: bool,
+    sprite: SpriteOptions,
+    options: DecodeOptions,
+) -> anyhow::Result<SpriteResult> {
+    if sprite.interval == 0 || sprite.columns == 0 || sprite.rows == 0 {
+        anyhow::bail!("sprite interval, columns and rows must be greater than zero");
+    }
+
+    let (video_name, save_path) =
+        decoder::output_dir(&video_path, save_path.as_ref(), options.video_track)?;
+
+    let interval = f64::from(sprite.interval);
+    let tiles_per_sheet = sprite.columns * sprite.rows;
+    let mut tiles: Vec<DynamicImage> = Vec::with_capacity(tiles_per_sheet);
+    let mut tile_size = None;
+    let mut font_settings = None;
+    let mut starts = Vec::new();
+    let mut sheet_paths = Vec::new();
+
+    let mut save_sheet = |tiles: Vec<DynamicImage>| -> anyhow::Result<()> {
+        let cols = tiles.len().min(sprite.columns);
+        let rows = tiles.len().div_ceil(sprite.columns);
+        let sheet = decoder::concat_tiles(tiles, cols, rows)?;
+        let sheet_path = save_path.join(sheet_name(&video_name, sheet_paths.len()));
+        sheet
+            .save(&sheet_path)
+            .with_context(|| format!("failed to save sprite sheet {}", sheet_path.display()))?;
+        sheet_paths.push(sheet_path.to_string_lossy().to_string());
+        Ok(())
+    };
+
+    // the whole video is sampled, the last frame ends the dump
+    let report = decoder::sample_frames_by_step(
+        &video_path,
+        0.0,
+        f64::INFINITY,
+        interval,
+        Some(sprite.tile_size),
+        options,
+        &AtomicBool::new(false),
+        |img, timestamp, target| {
+            let mut tile = DynamicImage::ImageRgba8(img);
+            // every tile of the sheets takes the size of the first one, the
+            // stream may change its resolution
+            let (width, height) = *tile_size.get_or_insert((tile.width(), tile.height()));
+            if (tile.width(), tile.height()) != (width, height) {
+                tile = tile.resize_exact(width, height, FilterType::Triangle);
+            }
+            if show_timestamp {
+                if font_settings.is_none() {
+                    font_settings = Some(decoder::get_font_settings(
+                        width as i32,
+                        height as i32,
+                        2.0,
+                    )?);
+                }
+                if let Some(font_settings) = font_settings.as_ref() {
+                    decoder::draw_timestamp(&mut tile, timestamp, font_settings);
+                }
+            }
+
+            tiles.push(tile);
+            starts.push(target);
+            if tiles.len() == tiles_per_sheet {
+                save_sheet(std::mem::take(&mut tiles))?;
+            }
+            Ok(())
+        },
+    )?;
+
+    if !tiles.is_empty() {
+        save_sheet(tiles)?;
+    }
+    let Some((width, height)) = tile_size else {
+        anyhow::bail!("could not decode any video frame");
+    };
+
+    let cues = sprite_cues(&starts, interval, width, height, &sprite);
+    let vtt_path = save_path.join(format!("{}-thumbnails.vtt", video_name));
+    fs::write(&vtt_path, webvtt(&cues, &video_name, width, height))
+        .with_context(|| format!("failed to save WebVTT track {}", vtt_path.display()))?;
+
+    Ok(SpriteResult {
+        file_name: video_name,
+        sheet_paths,
+        vtt_path: vtt_path.to_string_lossy().to_string(),
+        report,
+    })
+}
+
+fn sheet_name(video_name: &str, sheet: usize) -> String {
+    format!("{}-sprite-{}.jpeg", video_name, sheet)
+}
+
+/// Places the thumbnail of every start time on its sheet, left to right and
+/// top to bottom. A cue lasts until the next thumbnail.
+fn sprite_cues(
+    starts: &[f64],
+    interval: f64,
+    width: u32,
+    height: u32,
+    sprite: &SpriteOptions,
+) -> Vec<SpriteCue> {
+    let tiles_per_sheet = sprite.columns * sprite.rows;
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let tile = i % tiles_per_sheet;
+            let end = starts
+                .get(i + 1)
+                .map_or(start + interval, |&next| next.min(start + interval));
+            SpriteCue {
+                start,
+                end,
+                sheet: i / tiles_per_sheet,
+                x: (tile % sprite.columns) as u32 * width,
+                y: (tile / sprite.columns) as u32 * height,
+            }
+        })
+        .collect()
+}
+
+fn webvtt(cues: &[SpriteCue], video_name: &str, width: u32, height: u32) -> String {
+    let mut vtt = String::from("WEBVTT\n");
+    for cue in cues {
+        let _ = write!(
+            vtt,
+            "\n{} --> {}\n{}#xywh={},{},{},{}\n",
+            vtt_timestamp(cue.start),
+            vtt_timestamp(cue.end),
+            sheet_name(video_name, cue.sheet),
+            cue.x,
+            cue.y,
+            width,
+            height
+        );
+    }
+    vtt
+}
+
+fn vtt_timestamp(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprite_cues() {
+        let sprite = SpriteOptions {
+            interval: 10,
+            columns: 2,
+            rows: 2,
+            ..Default::default()
+        };
+        let starts = [0.0, 10.0, 20.0, 30.0, 40.0];
+        let cues = sprite_cues(&starts, 10.0, 160, 90, &sprite);
+        let vtt = webvtt(&cues[..1], "vid", 160, 90);
+
+        // Assert
+        assert_eq!(
+            cues[3],
+            SpriteCue {
+                start: 30.0,
+                end: 40.0,
+                sheet: 0,
+                x: 160,
+                y: 90,
+            }
+        );
+        assert_eq!(cues[4].sheet, 1);
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n00:00:00.000 --> 00:00:10.000\nvid-sprite-0.jpeg#xywh=0,0,160,90\n"
+        );
+    }
+}
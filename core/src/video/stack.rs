@@ -0,0 +1,373 @@
+use std::{path::Path, sync::atomic::AtomicBool};
+
+use anyhow::{self, Context};
+use image::{imageops::FilterType, DynamicImage, GrayImage, RgbImage};
+
+use super::decoder::{self, DecodeOptions, DecodeReport};
+
+/// Frames kept in memory by the median and max difference modes. Longer
+/// ranges are sampled evenly down to this count.
+const MAX_BUFFERED_FRAMES: usize = 64;
+/// Memory those frames may take, which lowers their count for large frames
+/// (20 frames at 4K).
+const MAX_BUFFERED_BYTES: usize = 512 * 1024 * 1024;
+/// Shift searched on a quarter-size copy of the frames, in quarter pixels.
+const COARSE_SEARCH: i32 = 8;
+const COARSE_SCALE: u32 = 4;
+/// Shift searched around the coarse estimate at full size.
+const FINE_SEARCH: i32 = 3;
+
+/// How the frames of a range are combined into a still.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StackMode {
+    /// Average of the frames, which lowers sensor noise on a static scene.
+    #[default]
+    Mean,
+    /// Median of the frames, which removes what only passes by.
+    Median,
+    /// Value furthest from the median, which keeps only what changed.
+    MaxDifference,
+}
+
+impl StackMode {
+    fn name(&self) -> &'static str {
+        match self {
+            StackMode::Mean => "mean",
+            StackMode::Median => "median",
+            StackMode::MaxDifference => "maxdiff",
+        }
+    }
+}
+
+/// Settings of a frame stack.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StackOptions {
+    pub mode: StackMode,
+    /// Aligns every frame on the first one, to compensate small camera shake.
+    pub register: bool,
+    pub decode: DecodeOptions,
+}
+
+#[derive(Debug)]
+pub struct StackResult {
+    pub file_name: String,
+    pub image_path: String,
+    pub frames: usize,
+    pub report: DecodeReport,
+}
+
+/// Combines the frames sampled at `frame_rate` between `time_start` and
+/// `time_end` into a single still, saved in a directory named after the
+/// video.
+pub fn stack_frames<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    time_start: f64,
+    time_end: f64,
+    frame_rate: u32,
+    options: StackOptions,
+) -> anyhow::Result<StackResult> {
+    if frame_rate == 0 {
+        anyhow::bail!("frame rate must be greater than zero");
+    }
+    if time_start >= time_end {
+        anyhow::bail!("the range must end after it starts");
+    }
+
+    let (video_name, save_path) =
+        decoder::output_dir(&video_path, save_path.as_ref(), options.decode.video_track)?;
+
+    let mut step = 1.0 / f64::from(frame_rate);
+    if options.mode != StackMode::Mean {
+        step = step.max((time_end - time_start) / MAX_BUFFERED_FRAMES as f64);
+    }
+
+    let mut stack = Stack::new(options.mode);
+    let mut reference: Option<GrayImage> = None;
+    let report = decoder::sample_frames_by_step(
+        &video_path,
+        time_start,
+        time_end,
+        step,
+        None,
+        options.decode,
+        &AtomicBool::new(false),
+        |img, _, _| {
+            let mut img = DynamicImage::ImageRgba8(img).into_rgb8();
+            if options.register {
+                let luma = DynamicImage::ImageRgb8(img.clone()).into_luma8();
+                match reference.as_ref() {
+                    Some(reference) => {
+                        let (dx, dy) = estimate_shift(reference, &luma);
+                        img = translate(&img, dx, dy);
+                    }
+                    None => reference = Some(luma),
+                }
+            }
+            stack.push(img);
+            Ok(())
+        },
+    )?;
+
+    let frames = stack.frames();
+    let Some(img) = stack.finish() else {
+        anyhow::bail!("could not decode any video frame");
+    };
+
+    let image_path = save_path.join(format!(
+        "{}-{}-{}s-{}s.png",
+        video_name,
+        options.mode.name(),
+        time_start,
+        time_end
+    ));
+    img.save(&image_path)
+        .with_context(|| format!("failed to save image {}", image_path.display()))?;
+
+    Ok(StackResult {
+        file_name: video_name,
+        image_path: image_path.to_string_lossy().to_string(),
+        frames,
+        report,
+    })
+}
+
+/// Frames combined so far. The mean is accumulated as the frames arrive,
+/// the other modes need every frame.
+struct Stack {
+    mode: StackMode,
+    frames: usize,
+    width: u32,
+    height: u32,
+    sum: Vec<u64>,
+    buffered: Vec<RgbImage>,
+    /// Frames kept by the buffered modes, lowered for large frames.
+    max_buffered: usize,
+    /// Every how many frames one is buffered, doubled whenever the buffer
+    /// is full so the kept frames stay spread over the range.
+    buffer_stride: usize,
+}
+
+impl Stack {
+    fn new(mode: StackMode) -> Self {
+        Self {
+            mode,
+            frames: 0,
+            width: 0,
+            height: 0,
+            sum: Vec::new(),
+            buffered: Vec::new(),
+            max_buffered: MAX_BUFFERED_FRAMES,
+            buffer_stride: 1,
+        }
+    }
+
+    fn push(&mut self, img: RgbImage) {
+        if self.frames == 0 {
+            (self.width, self.height) = img.dimensions();
+            if self.mode == StackMode::Mean {
+                self.sum = vec![0; img.as_raw().len()];
+            }
+            let frame_bytes = img.as_raw().len().max(1);
+            self.max_buffered = (MAX_BUFFERED_BYTES / frame_bytes).clamp(1, self.max_buffered);
+        } else if img.dimensions() != (self.width, self.height) {
+            // a resolution change mid-stream can not be stacked
+            return;
+        }
+        let index = self.frames;
+        self.frames += 1;
+
+        match self.mode {
+            StackMode::Mean => {
+                for (sum, &value) in self.sum.iter_mut().zip(img.as_raw()) {
+                    *sum += u64::from(value);
+                }
+            }
+            StackMode::Median | StackMode::MaxDifference => {
+                if index % self.buffer_stride != 0 {
+                    return;
+                }
+                self.buffered.push(img);
+                if self.buffered.len() > self.max_buffered {
+                    self.buffered = std::mem::take(&mut self.buffered)
+                        .into_iter()
+                        .step_by(2)
+                        .collect();
+                    self.buffer_stride *= 2;
+                }
+            }
+        }
+    }
+
+    /// Frames the still is made of.
+    fn frames(&self) -> usize {
+        match self.mode {
+            StackMode::Mean => self.frames,
+            StackMode::Median | StackMode::MaxDifference => self.buffered.len(),
+        }
+    }
+
+    fn finish(self) -> Option<RgbImage> {
+        if self.frames == 0 {
+            return None;
+        }
+
+        let data: Vec<u8> = match self.mode {
+            StackMode::Mean => {
+                let frames = self.frames as u64;
+                self.sum
+                    .iter()
+                    .map(|&sum| ((sum + frames / 2) / frames) as u8)
+                    .collect()
+            }
+            StackMode::Median | StackMode::MaxDifference => {
+                let len = self.buffered[0].as_raw().len();
+                let mut values = Vec::with_capacity(self.buffered.len());
+                (0..len)
+                    .map(|i| {
+                        values.clear();
+                        values.extend(self.buffered.iter().map(|img| img.as_raw()[i]));
+                        values.sort_unstable();
+                        let median = values[values.len() / 2];
+                        if self.mode == StackMode::Median {
+                            median
+                        } else {
+                            values
+                                .iter()
+                                .copied()
+                                .max_by_key(|value| value.abs_diff(median))
+                                .unwrap_or(median)
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        RgbImage::from_raw(self.width, self.height, data)
+    }
+}
+
+/// Shift (dx, dy) that best lines `frame` up with `reference`, searched on a
+/// quarter-size copy first and refined at full size.
+fn estimate_shift(reference: &GrayImage, frame: &GrayImage) -> (i32, i32) {
+    let (width, height) = reference.dimensions();
+    let small_width = (width / COARSE_SCALE).max(1);
+    let small_height = (height / COARSE_SCALE).max(1);
+    let small_reference =
+        image::imageops::resize(reference, small_width, small_height, FilterType::Triangle);
+    let small_frame =
+        image::imageops::resize(frame, small_width, small_height, FilterType::Triangle);
+
+    let (dx, dy) = best_shift(&small_reference, &small_frame, (0, 0), COARSE_SEARCH, 1);
+    let coarse = (dx * COARSE_SCALE as i32, dy * COARSE_SCALE as i32);
+    best_shift(reference, frame, coarse, FINE_SEARCH, 2)
+}
+
+/// Searches the shifts within `radius` of `center` for the lowest mean
+/// absolute difference, comparing every `stride` pixel.
+fn best_shift(
+    reference: &GrayImage,
+    frame: &GrayImage,
+    center: (i32, i32),
+    radius: i32,
+    stride: usize,
+) -> (i32, i32) {
+    let (width, height) = (reference.width() as i32, reference.height() as i32);
+    let mut best = (center, f64::MAX);
+
+    for dy in center.1 - radius..=center.1 + radius {
+        for dx in center.0 - radius..=center.0 + radius {
+            let mut total = 0u64;
+            let mut count = 0u64;
+            for y in (0.max(-dy)..height.min(height - dy)).step_by(stride) {
+                for x in (0.max(-dx)..width.min(width - dx)).step_by(stride) {
+                    let a = reference.get_pixel(x as u32, y as u32)[0];
+                    let b = frame.get_pixel((x + dx) as u32, (y + dy) as u32)[0];
+                    total += u64::from(a.abs_diff(b));
+                    count += 1;
+                }
+            }
+            // shifts leaving less than half of the frame are not trusted
+            if count * 2 < (width * height) as u64 / (stride * stride) as u64 {
+                continue;
+            }
+            let score = total as f64 / count as f64;
+            if score < best.1 {
+                best = ((dx, dy), score);
+            }
+        }
+    }
+
+    best.0
+}
+
+/// Moves the content of `img` so the pixel at (dx, dy) lands on the origin,
+/// repeating the edge pixels over the uncovered border.
+fn translate(img: &RgbImage, dx: i32, dy: i32) -> RgbImage {
+    let (width, height) = img.dimensions();
+    RgbImage::from_fn(width, height, |x, y| {
+        let source_x = (x as i32 + dx).clamp(0, width as i32 - 1) as u32;
+        let source_y = (y as i32 + dy).clamp(0, height as i32 - 1) as u32;
+        *img.get_pixel(source_x, source_y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack(mode: StackMode, values: &[u8]) -> Stack {
+        let mut stack = Stack::new(mode);
+        for &value in values {
+            stack.push(RgbImage::from_pixel(2, 2, image::Rgb([value, value, 255])));
+        }
+        stack
+    }
+
+    #[test]
+    fn test_stack_finish() {
+        let mean = stack(StackMode::Mean, &[10, 20, 40]).finish().unwrap();
+        let median = stack(StackMode::Median, &[10, 200, 30]).finish().unwrap();
+        let max_difference = stack(StackMode::MaxDifference, &[10, 200, 30, 20])
+            .finish()
+            .unwrap();
+
+        // Assert
+        assert_eq!(mean.get_pixel(1, 1).0, [23, 23, 255]);
+        // a value that only passes by is removed
+        assert_eq!(median.get_pixel(1, 1).0, [30, 30, 255]);
+        // only the change is kept
+        assert_eq!(max_difference.get_pixel(1, 1).0, [200, 200, 255]);
+        assert!(Stack::new(StackMode::Median).finish().is_none());
+    }
+
+    #[test]
+    fn test_stack_buffer_limit() {
+        let mut stack = Stack::new(StackMode::Median);
+        stack.max_buffered = 4;
+        for value in 0..10u8 {
+            stack.push(RgbImage::from_pixel(1, 1, image::Rgb([value; 3])));
+        }
+        let kept: Vec<u8> = stack.buffered.iter().map(|img| img.as_raw()[0]).collect();
+
+        // Assert
+        assert_eq!(stack.frames(), 3);
+        assert_eq!(kept, vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn test_estimate_shift() {
+        // deterministic texture, so a single shift matches
+        let pattern = |x: i32, y: i32| {
+            let hash = (x.wrapping_mul(73_856_093) ^ y.wrapping_mul(19_349_663)) as u32;
+            image::Luma([(hash.wrapping_mul(2_654_435_761) >> 24) as u8])
+        };
+        let reference = GrayImage::from_fn(128, 96, |x, y| pattern(x as i32 / 2, y as i32 / 2));
+        let frame = GrayImage::from_fn(128, 96, |x, y| {
+            pattern((x as i32 - 3) / 2, (y as i32 + 2) / 2)
+        });
+
+        // Assert
+        assert_eq!(estimate_shift(&reference, &frame), (3, -2));
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{self, Context};
+use image::{imageops::FilterType, DynamicImage, GrayImage, RgbaImage};
+
+use super::{
+    decoder::{self, DecodeOptions, DecodeReport},
+    writer::{VideoWriter, MP4_ENCODERS},
+};
+
+/// Size of the grayscale thumbnails compared to find motion.
+const MOTION_SIGNATURE_SIZE: u32 = 64;
+/// Mean difference between two thumbnails, from 0 to 255, above which a
+/// sample is taken as motion. Sensor noise and compression stay below it.
+const MOTION_THRESHOLD: f64 = 6.0;
+
+/// Settings of a time-lapse summary.
+#[derive(Debug, Clone, Copy)]
+pub struct TimelapseOptions {
+    /// Seconds of the recording between two frames of the summary.
+    pub interval: u32,
+    /// Keeps only the samples that differ from the previous one.
+    pub motion_only: bool,
+    /// Frames shown per second in the summary.
+    pub frame_rate: u32,
+    /// Burns the time of the recording into every frame.
+    pub show_timestamp: bool,
+    pub decode: DecodeOptions,
+}
+
+#[derive(Debug)]
+pub struct TimelapseResult {
+    pub file_name: String,
+    pub timelapse_path: String,
+    pub frames: usize,
+    /// The job was stopped, the summary covers the recording up to that point.
+    pub stopped: bool,
+    pub report: DecodeReport,
+}
+
+/// Encodes a frame every `interval` seconds of the recording into a short
+/// MP4, until the end of the file or until `stop` is set.
+pub fn export_timelapse<P: AsRef<Path>>(
+    video_path: P,
+    save_path: P,
+    options: TimelapseOptions,
+    stop: &AtomicBool,
+) -> anyhow::Result<TimelapseResult> {
+    if options.interval == 0 {
+        anyhow::bail!("time-lapse interval must be greater than zero");
+    }
+
+    let (video_name, save_path) =
+        decoder::output_dir(&video_path, save_path.as_ref(), options.decode.video_track)?;
+    let timelapse_path = save_path.join(format!(
+        "{}_timelapse_{}s.mp4",
+        video_name, options.interval
+    ));
+
+    // the encoder is opened with the size of the first frame
+    let mut writer: Option<VideoWriter> = None;
+    let mut frame_size = None;
+    let mut font_settings = None;
+    let mut previous_signature: Option<GrayImage> = None;
+
+    let report = decoder::sample_frames_by_step(
+        &video_path,
+        0.0,
+        f64::INFINITY,
+        f64::from(options.interval),
+        None,
+        options.decode,
+        stop,
+        |img, timestamp, _| {
+            if options.motion_only {
+                let signature = motion_signature(&img);
+                let is_motion = match previous_signature.as_ref() {
+                    Some(previous) => mean_difference(previous, &signature) > MOTION_THRESHOLD,
+                    None => true,
+                };
+                previous_signature = Some(signature);
+                if !is_motion {
+                    return Ok(());
+                }
+            }
+
+            let mut img = DynamicImage::ImageRgba8(img);
+            // recorders may change the resolution mid-stream, while every
+            // frame of the video has the size of the first one
+            let (width, height) = *frame_size.get_or_insert((img.width(), img.height()));
+            if (img.width(), img.height()) != (width, height) {
+                img = img.resize_exact(width, height, FilterType::Triangle);
+            }
+            if options.show_timestamp {
+                if font_settings.is_none() {
+                    font_settings = Some(decoder::get_font_settings(
+                        width as i32,
+                        height as i32,
+                        0.7,
+                    )?);
+                }
+                if let Some(font_settings) = font_settings.as_ref() {
+                    decoder::draw_timestamp(&mut img, timestamp, font_settings);
+                }
+            }
+
+            if writer.is_none() {
+                writer = Some(VideoWriter::new(
+                    &timelapse_path,
+                    MP4_ENCODERS,
+                    width,
+                    height,
+                    options.frame_rate,
+                )?);
+            }
+            match writer.as_mut() {
+                Some(writer) => writer.write(&img.into_rgba8()),
+                None => Ok(()),
+            }
+        },
+    );
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            // writes the trailer, so the frames encoded so far stay playable
+            if let Some(writer) = writer {
+                let _ = writer.finish();
+            }
+            return Err(err);
+        }
+    };
+
+    let Some(writer) = writer else {
+        anyhow::bail!("could not decode any video frame");
+    };
+    let frames = writer.frames();
+    writer
+        .finish()
+        .with_context(|| format!("failed to save time-lapse {}", timelapse_path.display()))?;
+
+    Ok(TimelapseResult {
+        file_name: video_name,
+        timelapse_path: timelapse_path.to_string_lossy().to_string(),
+        frames,
+        stopped: stop.load(Ordering::Relaxed),
+        report,
+    })
+}
+
+/// Small grayscale copy of a frame, which hides noise when comparing frames.
+fn motion_signature(img: &RgbaImage) -> GrayImage {
+    let thumb = image::imageops::resize(
+        img,
+        MOTION_SIGNATURE_SIZE,
+        MOTION_SIGNATURE_SIZE,
+        FilterType::Triangle,
+    );
+    DynamicImage::ImageRgba8(thumb).into_luma8()
+}
+
+fn mean_difference(a: &GrayImage, b: &GrayImage) -> f64 {
+    let total: u64 = a
+        .as_raw()
+        .iter()
+        .zip(b.as_raw())
+        .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+        .sum();
+    total as f64 / a.as_raw().len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_timelapse() {
+        let filename = "../data/video/vid.mp4";
+        let save_path = std::env::temp_dir().join("vimpeg-timelapse-test");
+        let options = TimelapseOptions {
+            interval: 1,
+            motion_only: false,
+            frame_rate: 10,
+            show_timestamp: true,
+            decode: DecodeOptions::default(),
+        };
+        let result = export_timelapse(
+            Path::new(filename),
+            save_path.as_path(),
+            options,
+            &AtomicBool::new(false),
+        )
+        .expect("Failed to export the time-lapse.");
+
+        // Assert
+        assert!(result.frames > 0);
+        assert!(!result.stopped);
+        assert!(Path::new(&result.timelapse_path).exists());
+    }
+}
@@ -0,0 +1,190 @@
+use ffmpeg_next::{self as ffmpeg};
+
+use super::input::InputOptions;
+
+/// Forward jumps between consecutive frames larger than this (seconds) are
+/// reported as timestamp discontinuities.
+const DISCONTINUITY_THRESHOLD: f64 = 10.0;
+/// Backward jumps larger than this (seconds) are reported and corrected.
+const BACKWARD_THRESHOLD: f64 = 1.0;
+/// Frame duration used when the stream does not declare a frame rate.
+const DEFAULT_FRAME_DURATION: f64 = 1.0 / 25.0;
+
+/// Maps frame timestamps to seconds since the start of the stream.
+///
+/// Timestamps are unwrapped when they overflow the bits stored by the
+/// container (33 bits for MPEG-TS). Backward jumps can not happen in a
+/// recording, so they are joined to the previous frame, while forward jumps
+/// are kept as they may be real pauses of the recorder. Both are counted.
+///
+/// Streams without usable timestamps can use synthetic ones, one frame
+/// duration apart (half of it for field rate deinterlacing). They are counted
+/// from the first frame of the file, so such streams are decoded from the
+/// start instead of seeked.
+pub(crate) struct Timeline {
+    start: f64,
+    wrap: Option<f64>,
+    frame_duration: f64,
+    offset: f64,
+    last: Option<f64>,
+    discontinuities: usize,
+    /// Time of the next frame, when the timestamps are synthetic.
+    synthetic: Option<f64>,
+}
+
+impl Timeline {
+    pub(crate) fn new(stream: &ffmpeg::format::stream::Stream, input: &InputOptions) -> Self {
+        let time_base = f64::from(stream.time_base());
+        let start = match stream.start_time() {
+            ffmpeg::ffi::AV_NOPTS_VALUE => 0f64,
+            start_time => start_time as f64 * time_base,
+        };
+        let wrap_bits = unsafe { (*stream.as_ptr()).pts_wrap_bits };
+        let wrap =
+            (wrap_bits > 0 && wrap_bits < 63).then(|| (1i64 << wrap_bits) as f64 * time_base);
+        let frame_duration = match (input.frame_rate, stream.avg_frame_rate()) {
+            (Some(frame_rate), _) if frame_rate > 0.0 => 1.0 / frame_rate,
+            (_, rate) if rate.numerator() > 0 && rate.denominator() > 0 => 1.0 / f64::from(rate),
+            _ => DEFAULT_FRAME_DURATION,
+        };
+
+        let mut timeline = Self::with_start(start, wrap, frame_duration);
+        if input.generate_timestamps {
+            timeline.synthetic = Some(0f64);
+        }
+        timeline
+    }
+
+    fn with_start(start: f64, wrap: Option<f64>, frame_duration: f64) -> Self {
+        Self {
+            start,
+            wrap,
+            frame_duration,
+            offset: 0f64,
+            last: None,
+            discontinuities: 0,
+            synthetic: None,
+        }
+    }
+
+    /// Start time of the stream in seconds, as stored by the container.
+    pub(crate) fn start(&self) -> f64 {
+        self.start
+    }
+
+    pub(crate) fn discontinuities(&self) -> usize {
+        self.discontinuities
+    }
+
+    /// Position (`AV_TIME_BASE` units) to seek to for `time` seconds since the
+    /// start of the stream. Synthetic timestamps count the frames from the
+    /// start of the file, so such streams are never seeked.
+    pub(crate) fn seek_position(&self, time: f64) -> Option<i64> {
+        if time <= 0.0 || self.synthetic.is_some() {
+            return None;
+        }
+        Some(((self.start + time) * f64::from(ffmpeg::ffi::AV_TIME_BASE)) as i64)
+    }
+
+    /// Forgets the previous frame, after a seek.
+    pub(crate) fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Converts a timestamp (seconds, as stored in the stream) to seconds
+    /// since the start of the stream. `rate_factor` is the number of frames
+    /// output by the deinterlacer for every decoded frame.
+    pub(crate) fn normalize(&mut self, timestamp: f64, rate_factor: u32) -> f64 {
+        if let Some(next) = self.synthetic.as_mut() {
+            let time = *next;
+            *next += self.frame_duration / f64::from(rate_factor.max(1));
+            return time;
+        }
+
+        let mut time = timestamp + self.offset - self.start;
+
+        if let Some(last) = self.last {
+            let delta = time - last;
+            match self.wrap {
+                Some(wrap) if delta < -wrap / 2.0 => {
+                    self.offset += wrap;
+                    time += wrap;
+                }
+                _ if delta < -BACKWARD_THRESHOLD => {
+                    let shift = last + self.frame_duration - time;
+                    self.offset += shift;
+                    time += shift;
+                    self.discontinuities += 1;
+                }
+                _ if delta > DISCONTINUITY_THRESHOLD => {
+                    self.discontinuities += 1;
+                }
+                _ => {}
+            }
+        }
+
+        self.last = Some(time);
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_start_and_wrap() {
+        // MPEG-TS: 33 bits at 90 kHz wraps after ~26.5 hours
+        let wrap = (1i64 << 33) as f64 / 90000.0;
+        let mut timeline = Timeline::with_start(wrap - 1.0, Some(wrap), 0.04);
+
+        // Assert
+        assert_eq!(timeline.normalize(wrap - 1.0, 1), 0.0);
+        assert!((timeline.normalize(0.5, 1) - 1.5).abs() < 1e-6);
+        assert_eq!(timeline.discontinuities(), 0);
+    }
+
+    #[test]
+    fn test_timeline_discontinuities() {
+        let mut timeline = Timeline::with_start(0.0, None, 0.04);
+        timeline.normalize(100.0, 1);
+        let time = timeline.normalize(2.0, 1);
+        timeline.normalize(200.0, 1);
+
+        // Assert
+        assert!((time - 100.04).abs() < 1e-6);
+        assert_eq!(timeline.discontinuities(), 2);
+    }
+
+    #[test]
+    fn test_timeline_synthetic() {
+        let mut timeline = Timeline::with_start(5.0, None, 0.5);
+        timeline.synthetic = Some(0.0);
+        timeline.normalize(0.0, 1);
+        let time = timeline.normalize(0.0, 1);
+        // field rate deinterlacing outputs two frames per decoded frame
+        let field_time = timeline.normalize(0.0, 2);
+        let next_time = timeline.normalize(0.0, 2);
+
+        // Assert
+        assert_eq!(time, 0.5);
+        assert_eq!(field_time, 1.0);
+        assert_eq!(next_time, 1.25);
+    }
+
+    #[test]
+    fn test_timeline_seek_position() {
+        let timeline = Timeline::with_start(2.0, None, 0.04);
+        let mut synthetic = Timeline::with_start(2.0, None, 0.04);
+        synthetic.synthetic = Some(0.0);
+
+        // Assert
+        assert_eq!(
+            timeline.seek_position(10.0),
+            Some(12 * i64::from(ffmpeg::ffi::AV_TIME_BASE))
+        );
+        assert_eq!(timeline.seek_position(0.0), None);
+        // the first frame after a seek would be labelled 0
+        assert_eq!(synthetic.seek_position(10.0), None);
+    }
+}
@@ -0,0 +1,228 @@
+use ffmpeg::util::{color, frame};
+use ffmpeg_next::{self as ffmpeg};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+
+/// Reference white of the SDR output, in nits (ITU-R BT.2408).
+const SDR_WHITE: f32 = 203.0;
+/// Peak brightness assumed for HDR sources, in nits.
+const HDR_PEAK: f32 = 1000.0;
+
+/// Operator used to compress the HDR luminance into the SDR range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToneMap {
+    /// No tone mapping, HDR values are clipped by the scaler.
+    Off,
+    /// Luminance above the SDR white is clipped.
+    Clip,
+    Reinhard,
+    #[default]
+    Hable,
+    Mobius,
+}
+
+/// HDR transfer function of a video stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HdrTransfer {
+    /// SMPTE ST 2084 (HDR10).
+    Pq,
+    /// ARIB STD-B67 (Hybrid Log-Gamma).
+    Hlg,
+}
+
+impl HdrTransfer {
+    pub(crate) fn from_characteristic(
+        transfer: color::TransferCharacteristic,
+    ) -> Option<HdrTransfer> {
+        match transfer {
+            color::TransferCharacteristic::SMPTE2084 => Some(HdrTransfer::Pq),
+            color::TransferCharacteristic::ARIB_STD_B67 => Some(HdrTransfer::Hlg),
+            _ => None,
+        }
+    }
+
+    /// Linear light relative to the SDR white for a non-linear signal value (0.0 - 1.0).
+    fn to_linear(self, value: f32) -> f32 {
+        match self {
+            HdrTransfer::Pq => {
+                const M1: f32 = 2610.0 / 16384.0;
+                const M2: f32 = 2523.0 / 4096.0 * 128.0;
+                const C1: f32 = 3424.0 / 4096.0;
+                const C2: f32 = 2413.0 / 4096.0 * 32.0;
+                const C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+                let power = value.max(0.0).powf(1.0 / M2);
+                let nits = 10000.0 * ((power - C1).max(0.0) / (C2 - C3 * power)).powf(1.0 / M1);
+                nits / SDR_WHITE
+            }
+            HdrTransfer::Hlg => {
+                const A: f32 = 0.178_833;
+                const B: f32 = 0.284_669;
+                const C: f32 = 0.559_911;
+
+                let scene = if value <= 0.5 {
+                    value * value / 3.0
+                } else {
+                    (((value - C) / A).exp() + B) / 12.0
+                };
+                scene * HDR_PEAK / SDR_WHITE
+            }
+        }
+    }
+}
+
+/// Converts 16 bit BT.2020 HDR frames to 8 bit BT.709 SDR images.
+pub(crate) struct ToneMapper {
+    tone_map: ToneMap,
+    transfer: HdrTransfer,
+    peak: f32,
+    /// Linear light for every 16 bit signal value.
+    linear: Vec<f32>,
+}
+
+impl ToneMapper {
+    pub(crate) fn new(tone_map: ToneMap, transfer: HdrTransfer) -> Self {
+        let linear = (0..=u16::MAX)
+            .map(|value| transfer.to_linear(f32::from(value) / f32::from(u16::MAX)))
+            .collect();
+
+        Self {
+            tone_map,
+            transfer,
+            peak: HDR_PEAK / SDR_WHITE,
+            linear,
+        }
+    }
+
+    pub(crate) fn transfer(&self) -> HdrTransfer {
+        self.transfer
+    }
+
+    /// Tone maps an RGBA64LE frame.
+    pub(crate) fn run(&self, rgb_frame: &frame::Video) -> RgbaImage {
+        let width = rgb_frame.width();
+        let height = rgb_frame.height();
+        let stride = rgb_frame.stride(0);
+        let data = rgb_frame.data(0);
+
+        let mut img = RgbaImage::new(width, height);
+        for (y, row) in data.chunks(stride).take(height as usize).enumerate() {
+            for x in 0..width as usize {
+                let pixel = &row[x * 8..x * 8 + 8];
+                let sample = |i: usize| u16::from_le_bytes([pixel[i * 2], pixel[i * 2 + 1]]);
+                let rgb = self.map_pixel([
+                    self.linear[sample(0) as usize],
+                    self.linear[sample(1) as usize],
+                    self.linear[sample(2) as usize],
+                ]);
+                let alpha = (sample(3) >> 8) as u8;
+                img.put_pixel(
+                    x as u32,
+                    y as u32,
+                    image::Rgba([rgb[0], rgb[1], rgb[2], alpha]),
+                );
+            }
+        }
+
+        img
+    }
+
+    fn map_pixel(&self, rgb: [f32; 3]) -> [u8; 3] {
+        let mut rgb = if self.transfer == HdrTransfer::Hlg {
+            hlg_ootf(rgb)
+        } else {
+            rgb
+        };
+        rgb = bt2020_to_bt709(rgb);
+
+        // tone map the luminance and scale the channels by the same ratio,
+        // so the hue is preserved
+        let luma = 0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2];
+        if luma > 0.0 {
+            let ratio = self.tone_map(luma) / luma;
+            rgb.iter_mut().for_each(|value| *value *= ratio);
+        }
+
+        rgb.map(|value| (value.clamp(0.0, 1.0).powf(1.0 / 2.4) * 255.0).round() as u8)
+    }
+
+    fn tone_map(&self, luma: f32) -> f32 {
+        let peak = self.peak;
+        match self.tone_map {
+            ToneMap::Off | ToneMap::Clip => luma.min(1.0),
+            ToneMap::Reinhard => luma * (1.0 + luma / (peak * peak)) / (1.0 + luma),
+            ToneMap::Hable => hable(luma) / hable(peak),
+            ToneMap::Mobius => {
+                const J: f32 = 0.3;
+                if luma <= J {
+                    return luma;
+                }
+                let a = -J * J * (peak - 1.0) / (J * J - 2.0 * J + peak);
+                let b = (J * J - 2.0 * J * peak + peak) / (peak - 1.0).max(1e-6);
+                (b * b + 2.0 * b * J + J * J) / (b - a) * (luma + a) / (luma + b)
+            }
+        }
+    }
+}
+
+fn hable(value: f32) -> f32 {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+
+    (value * (A * value + C * B) + D * E) / (value * (A * value + B) + D * F) - E / F
+}
+
+/// HLG system gamma, for a display with the assumed peak brightness.
+fn hlg_ootf(rgb: [f32; 3]) -> [f32; 3] {
+    const GAMMA: f32 = 1.2;
+
+    let peak = HDR_PEAK / SDR_WHITE;
+    let luma = (0.2627 * rgb[0] + 0.6780 * rgb[1] + 0.0593 * rgb[2]) / peak;
+    if luma <= 0.0 {
+        return [0.0; 3];
+    }
+    let gain = luma.powf(GAMMA - 1.0);
+    rgb.map(|value| value * gain)
+}
+
+fn bt2020_to_bt709(rgb: [f32; 3]) -> [f32; 3] {
+    [
+        1.6605 * rgb[0] - 0.5876 * rgb[1] - 0.0728 * rgb[2],
+        -0.1246 * rgb[0] + 1.1329 * rgb[1] - 0.0083 * rgb[2],
+        -0.0182 * rgb[0] - 0.1006 * rgb[1] + 1.1187 * rgb[2],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pq_to_linear() {
+        // 203 nits (SDR white) is encoded as 0.58 in PQ
+        let linear = HdrTransfer::Pq.to_linear(0.5807);
+        assert!((linear - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tone_map_operators() {
+        for tone_map in [
+            ToneMap::Clip,
+            ToneMap::Reinhard,
+            ToneMap::Hable,
+            ToneMap::Mobius,
+        ] {
+            let tone_mapper = ToneMapper::new(tone_map, HdrTransfer::Pq);
+            let peak = tone_mapper.tone_map(tone_mapper.peak);
+
+            // Assert
+            assert!((peak - 1.0).abs() < 0.01, "{tone_map:?}");
+            assert!(tone_mapper.tone_map(0.5) <= 0.5);
+        }
+    }
+}
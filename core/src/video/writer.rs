@@ -0,0 +1,159 @@
+use std::path::Path;
+
+use anyhow::{self, Context};
+use ffmpeg::{codec, format, software::scaling, util::frame, Packet, Rational};
+use ffmpeg_next::{self as ffmpeg};
+use image::RgbaImage;
+
+/// Encoders tried in order for each video format, as not every FFmpeg build
+/// has the external libraries.
+pub(crate) const WEBP_ENCODERS: &[&str] = &["libwebp_anim", "libwebp"];
+pub(crate) const MP4_ENCODERS: &[&str] = &["libx264", "libopenh264", "h264_mf", "mpeg4"];
+
+/// Encodes RGBA images one by one into a video, in the container given by
+/// the extension of the output path.
+pub(crate) struct VideoWriter {
+    output_format_context: format::context::Output,
+    encoder: ffmpeg::encoder::video::Encoder,
+    scaler: scaling::Context,
+    stream_index: usize,
+    time_base: Rational,
+    stream_time_base: Rational,
+    source_width: u32,
+    source_height: u32,
+    frame_index: i64,
+}
+
+impl VideoWriter {
+    /// Opens the first available encoder of `encoders` for frames of
+    /// `width` x `height` shown at `frame_rate` per second.
+    pub(crate) fn new<P: AsRef<Path>>(
+        path: P,
+        encoders: &[&str],
+        width: u32,
+        height: u32,
+        frame_rate: u32,
+    ) -> anyhow::Result<Self> {
+        ffmpeg::init()?;
+
+        if frame_rate == 0 {
+            anyhow::bail!("frame rate must be greater than zero");
+        }
+
+        let codec = encoders
+            .iter()
+            .find_map(|name| ffmpeg::encoder::find_by_name(name))
+            .with_context(|| {
+                format!("none of the encoders {} is available", encoders.join(", "))
+            })?;
+
+        // 4:2:0 needs even dimensions
+        let encoded_width = (width & !1).max(2);
+        let encoded_height = (height & !1).max(2);
+        let time_base = Rational::new(1, frame_rate as i32);
+
+        let mut output_format_context = format::output(&path)?;
+        let global_header = output_format_context
+            .format()
+            .flags()
+            .contains(format::Flags::GLOBAL_HEADER);
+
+        let mut encoder = codec::context::Context::new_with_codec(codec)
+            .encoder()
+            .video()?;
+        encoder.set_width(encoded_width);
+        encoder.set_height(encoded_height);
+        encoder.set_format(format::Pixel::YUV420P);
+        encoder.set_time_base(time_base);
+        encoder.set_frame_rate(Some(Rational::new(frame_rate as i32, 1)));
+        if global_header {
+            encoder.set_flags(codec::Flags::GLOBAL_HEADER);
+        }
+        let encoder = encoder
+            .open_as(codec)
+            .context("failed to open the video encoder")?;
+
+        let mut stream = output_format_context.add_stream(codec)?;
+        stream.set_parameters(&encoder);
+        stream.set_time_base(time_base);
+        let stream_index = stream.index();
+
+        let mut dictionary = ffmpeg::Dictionary::new();
+        // read by the WebP muxer, which plays the animation once by default
+        dictionary.set("loop", "0");
+        output_format_context.write_header_with(dictionary)?;
+        let stream_time_base = output_format_context
+            .stream(stream_index)
+            .context("video stream not found")?
+            .time_base();
+
+        let scaler = scaling::Context::get(
+            format::Pixel::RGBA,
+            width,
+            height,
+            format::Pixel::YUV420P,
+            encoded_width,
+            encoded_height,
+            scaling::Flags::BICUBIC,
+        )?;
+
+        Ok(Self {
+            output_format_context,
+            encoder,
+            scaler,
+            stream_index,
+            time_base,
+            stream_time_base,
+            source_width: width,
+            source_height: height,
+            frame_index: 0,
+        })
+    }
+
+    /// Number of frames written so far.
+    pub(crate) fn frames(&self) -> usize {
+        self.frame_index as usize
+    }
+
+    pub(crate) fn write(&mut self, img: &RgbaImage) -> anyhow::Result<()> {
+        if img.dimensions() != (self.source_width, self.source_height) {
+            anyhow::bail!("every frame of a video must have the same size");
+        }
+
+        let mut converted = frame::Video::empty();
+        self.scaler.run(&rgba_frame(img), &mut converted)?;
+        converted.set_pts(Some(self.frame_index));
+        self.encoder.send_frame(&converted)?;
+        self.frame_index += 1;
+        self.write_packets()
+    }
+
+    /// Drains the encoder and closes the file.
+    pub(crate) fn finish(mut self) -> anyhow::Result<()> {
+        self.encoder.send_eof()?;
+        self.write_packets()?;
+        self.output_format_context.write_trailer()?;
+        Ok(())
+    }
+
+    fn write_packets(&mut self) -> anyhow::Result<()> {
+        let mut packet = Packet::empty();
+        while self.encoder.receive_packet(&mut packet).is_ok() {
+            packet.set_stream(self.stream_index);
+            packet.rescale_ts(self.time_base, self.stream_time_base);
+            packet.write_interleaved(&mut self.output_format_context)?;
+        }
+        Ok(())
+    }
+}
+
+fn rgba_frame(img: &RgbaImage) -> frame::Video {
+    let mut rgba = frame::Video::new(format::Pixel::RGBA, img.width(), img.height());
+    let stride = rgba.stride(0);
+    let row_size = img.width() as usize * 4;
+    let data = rgba.data_mut(0);
+    for (y, row) in img.as_raw().chunks_exact(row_size).enumerate() {
+        data[y * stride..y * stride + row_size].copy_from_slice(row);
+    }
+    rgba
+}
@@ -14,10 +14,58 @@ cancel = Cancel
 extract = Extract
 extract-frames = Extract frames
 extract-to-image = Extract to image
+capture = Capture
+capture-interval = Interval (seconds)
+capture-interval-description = Seconds between snapshots of the live stream or growing file
+hourly-sheet = Hourly contact sheet
+hourly-sheet-description = Roll the snapshots of every hour into a contact sheet
+sprites = Sprites
+sprite-interval = Interval (seconds)
+sprite-interval-description = Seconds between two thumbnails of the WebVTT track
+sprite-tile-size = Thumbnail size
+sprite-tile-size-description = Largest width and height of the thumbnails
+sprite-columns = Columns
+sprite-columns-description = Thumbnails per row of a sprite sheet
+sprite-rows = Rows
+sprite-rows-description = Rows of a sprite sheet, a new sheet is started once it is full
+heatmap = Activity
+heatmap-interval = Interval (seconds)
+heatmap-interval-description = Seconds between two compared frames of the motion heatmap and activity timeline
+timelapse = Time-lapse
+timelapse-interval = Interval (seconds)
+timelapse-interval-description = Seconds of the recording between two frames of the summary
+timelapse-frame-rate = Frame rate
+timelapse-frame-rate-description = Frames shown per second in the summary video
+motion-only = Motion only
+motion-only-description = Keep only the frames that changed since the previous sample
+slit-scan = Slit-scan
+slit-orientation = Slit
+slit-orientation-description = Line of the frame taken from every sample, a column for movement across the frame or a row for movement up or down
+slit-column = Column
+slit-row = Row
+slit-position = Position (%)
+slit-position-description = Place of the slit, from the left or top edge of the frame
+slit-frame-rate = Samples per second
+slit-frame-rate-description = Samples taken per second of the recording, each one becomes a pixel of the strip
+stop-capture = Stop capture
+stop-timelapse = Stop time-lapse
+export-clip = Export clip
+export-clip-description = Save the segment between the start and end times as a video file, with its hash and manifest
+precise-cut = Precise cut
+precise-cut-description = Re-encode the edges so the clip starts and ends at the exact times, instead of on keyframes
+stack-frames = Stack frames
+stack-frames-description = Combine the frames of the range into a single still instead of saving them one by one
+stack-off = Off
+stack-mean = Mean
+stack-median = Median
+stack-max-difference = Max difference
+register-frames = Register frames
+register-frames-description = Align every frame on the first one before stacking, to compensate small camera shake
 clear-image-list = Clear image list
 destination-directory = Destination directory
 search = Search
 directory = Directory
+directory-or-url = Directory or stream URL
 select-directory = Select directory
 
 # Toolbar
@@ -48,6 +96,38 @@ english = English
 portuguese = Portuguese
 spanish = Spanish
 message-dialog = The program must be restarted for the changes to take effect.
+thumbnail = Thumbnail
+thumbnail-position = Thumbnail position
+thumbnail-position-description = Percentage of the video where the thumbnail search begins
+deinterlace = Deinterlace
+deinterlace-description = Applied only to interlaced videos
+performance = Performance
+decoder-threads = Decoder threads
+decoder-threads-description = Threads used to decode each video, 0 uses all cores
+parallel-videos = Parallel videos
+parallel-videos-description = Videos extracted at the same time, 0 is automatic
+input = Input
+input-description = For raw elementary streams and DVR recordings that can not be opened
+input-format = Demuxer
+input-format-auto = Detect from the file
+input-frame-rate = Frame rate
+input-frame-rate-description = Frame rate of streams without timing, 0 reads it from the file
+generate-timestamps = Generate timestamps
+generate-timestamps-description = Replaces missing or broken timestamps using the frame rate
+network = Network
+network-description = For HTTP files, HLS playlists and RTSP cameras
+network-timeout = Timeout
+network-timeout-description = Seconds without data before a stream fails, 0 waits forever
+reconnect = Reconnect
+reconnect-description = Reconnects streams dropped by the server
+rtsp-tcp = RTSP over TCP
+rtsp-tcp-description = Reads cameras over TCP, UDP rarely gets through NAT or firewalls
+animated-preview = Animated preview
+animated-preview-description = Saves the sampled frames as an animation instead of a contact sheet
+preview-size = Size
+preview-size-description = Largest width and height of the preview frames
+preview-frame-rate = Playback rate
+preview-frame-rate-description = Frames shown per second
 
 # Extract dialog
 time-start = Time start
@@ -61,12 +141,64 @@ message-invalid-dst-dir = Invalid destination directory.
 message-invalid-duration = The initial duration must be greater than the final duration.
 message-invalid-frame-rate = Enter a valid value for Frame Rate.
 
+rotation-auto = Automatic
+flip-horizontal = Flip horizontal
+flip-vertical = Flip vertical
+deinterlace-off = Off
+deinterlace-fields = Separate fields
+deinterlace-bob = Bob
+deinterlace-quality = Quality
+tone-mapping = HDR tone mapping
+tone-map-off = Off
+tone-map-clip = Clip
+preview-off = Off
+tolerant-decoding = Tolerant decoding
+tolerant-decoding-description = Skip damaged packets and resume on the next keyframe
+video-tracks = Video tracks
+video-tracks-description = Video streams of the file to extract, the best one is used when none is checked
+track-number = Track { $number }
+tracks-side-by-side = Tracks side by side
+tracks-side-by-side-description = Puts the selected tracks side by side in a single contact sheet
+
+# Details dialog
+details = Details
+export-json = Export JSON
+save = Save
+container = Container
+format = Format
+bit-rate = Bit rate
+stream = Stream
+codec = Codec
+profile = Profile
+pixel-format = Pixel format
+resolution = Resolution
+rotation = Rotation
+field-order = Field order
+video-track = Video track
+sample-rate = Sample rate
+channels = Channels
+color = Color
+message-export-json-error = Failed to export the details to JSON.
+
+# Video card
+damaged = Damaged
+corrupt-packets = Corrupt packets
+corrupt-frames = Corrupt frames
+decoding-failed = Decoding stopped on an error
+discontinuities = Timestamp jumps
+
 # Errors
 generic-error = An error ocurred:
 open-image-error = Failed to open image
 open-video-error = Failed to open video
 invalid-directory = Invalid directory!
 select-one-video = Select at least one video.
+capture-running = A capture is already running.
+capture-interrupted = Capture interrupted, retrying
+clip-exported = Clip saved to
+preview-saved = Preview saved to
+timelapse-running = A time-lapse is already running.
+timelapse-saved = Time-lapse saved to
 
 # About dialog
 translators = Hericson dos Santos <hericson.cipol@gmail.com>
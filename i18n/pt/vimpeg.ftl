@@ -14,10 +14,58 @@ cancel = Cancelar
 extract = Extrair
 extract-frames = Extrair frames
 extract-to-image = Extrair para imagem
+capture = Capturar
+capture-interval = Intervalo (segundos)
+capture-interval-description = Segundos entre capturas da transmissão ao vivo ou do arquivo em gravação
+hourly-sheet = Folha de contato por hora
+hourly-sheet-description = Junta as capturas de cada hora em uma folha de contato
+sprites = Sprites
+sprite-interval = Intervalo (segundos)
+sprite-interval-description = Segundos entre duas miniaturas da trilha WebVTT
+sprite-tile-size = Tamanho da miniatura
+sprite-tile-size-description = Maior largura e altura das miniaturas
+sprite-columns = Colunas
+sprite-columns-description = Miniaturas por linha de uma folha de sprites
+sprite-rows = Linhas
+sprite-rows-description = Linhas de uma folha de sprites, uma nova folha é iniciada quando estiver cheia
+heatmap = Atividade
+heatmap-interval = Intervalo (segundos)
+heatmap-interval-description = Segundos entre dois quadros comparados do mapa de calor de movimento e da linha do tempo de atividade
+timelapse = Time-lapse
+timelapse-interval = Intervalo (segundos)
+timelapse-interval-description = Segundos da gravação entre dois quadros do resumo
+timelapse-frame-rate = Taxa de quadros
+timelapse-frame-rate-description = Quadros exibidos por segundo no vídeo de resumo
+motion-only = Somente movimento
+motion-only-description = Mantém apenas os quadros que mudaram desde a amostra anterior
+slit-scan = Varredura por fenda
+slit-orientation = Fenda
+slit-orientation-description = Linha do quadro lida em cada amostra, uma coluna para movimento de lado a lado ou uma linha para movimento de cima a baixo
+slit-column = Coluna
+slit-row = Linha
+slit-position = Posição (%)
+slit-position-description = Local da fenda, a partir da borda esquerda ou superior do quadro
+slit-frame-rate = Amostras por segundo
+slit-frame-rate-description = Amostras lidas por segundo da gravação, cada uma vira um pixel da faixa
+stop-capture = Parar captura
+stop-timelapse = Parar time-lapse
+export-clip = Exportar trecho
+export-clip-description = Salva o trecho entre os tempos inicial e final como arquivo de vídeo, com seu hash e manifesto
+precise-cut = Corte preciso
+precise-cut-description = Recodifica as bordas para o trecho começar e terminar nos tempos exatos, em vez de nos quadros-chave
+stack-frames = Empilhar quadros
+stack-frames-description = Combina os quadros do intervalo em uma única imagem em vez de salvá-los um a um
+stack-off = Desligado
+stack-mean = Média
+stack-median = Mediana
+stack-max-difference = Diferença máxima
+register-frames = Alinhar quadros
+register-frames-description = Alinha cada quadro ao primeiro antes de empilhar, para compensar pequenos tremores da câmera
 clear-image-list = Limpar lista de imagens
 destination-directory = Diretório de destino
 search = Pesquisar
 directory = Diretório
+directory-or-url = Diretório ou URL de fluxo
 select-directory = Selecionar diretório
 
 # Toolbar
@@ -48,6 +96,38 @@ english = Inglês
 portuguese = Português
 spanish = Espanhol
 message-dialog = O programa deve ser reiniciado para que as alterações tenham efeito.
+thumbnail = Miniatura
+thumbnail-position = Posição da miniatura
+thumbnail-position-description = Porcentagem do vídeo onde a busca da miniatura começa
+deinterlace = Desentrelaçar
+deinterlace-description = Aplicado somente a vídeos entrelaçados
+performance = Desempenho
+decoder-threads = Threads do decodificador
+decoder-threads-description = Threads usadas para decodificar cada vídeo, 0 usa todos os núcleos
+parallel-videos = Vídeos em paralelo
+parallel-videos-description = Vídeos extraídos ao mesmo tempo, 0 é automático
+input = Entrada
+input-description = Para fluxos elementares brutos e gravações de DVR que não podem ser abertos
+input-format = Demultiplexador
+input-format-auto = Detectar pelo arquivo
+input-frame-rate = Taxa de quadros
+input-frame-rate-description = Taxa de quadros de fluxos sem tempo, 0 a lê do arquivo
+generate-timestamps = Gerar marcas de tempo
+generate-timestamps-description = Substitui marcas de tempo ausentes ou corrompidas usando a taxa de quadros
+network = Rede
+network-description = Para arquivos HTTP, listas HLS e câmeras RTSP
+network-timeout = Tempo limite
+network-timeout-description = Segundos sem dados antes de um fluxo falhar, 0 espera indefinidamente
+reconnect = Reconectar
+reconnect-description = Reconecta fluxos interrompidos pelo servidor
+rtsp-tcp = RTSP sobre TCP
+rtsp-tcp-description = Lê câmeras por TCP, UDP raramente passa por NAT ou firewalls
+animated-preview = Prévia animada
+animated-preview-description = Salva os quadros amostrados como uma animação em vez de uma folha de contato
+preview-size = Tamanho
+preview-size-description = Maior largura e altura dos quadros da prévia
+preview-frame-rate = Velocidade de reprodução
+preview-frame-rate-description = Quadros exibidos por segundo
 
 # Extract dialog
 time-start = Hora de início
@@ -61,12 +141,64 @@ message-invalid-dst-dir = Diretório de destino inválido.
 message-invalid-duration = A duração inicial deve ser maior que a duração final.
 message-invalid-frame-rate = Insira um valor válido para Taxa de quadros.
 
+rotation-auto = Automática
+flip-horizontal = Espelhar horizontalmente
+flip-vertical = Espelhar verticalmente
+deinterlace-off = Desligado
+deinterlace-fields = Separar campos
+deinterlace-bob = Bob
+deinterlace-quality = Qualidade
+tone-mapping = Mapeamento de tons HDR
+tone-map-off = Desligado
+tone-map-clip = Cortar
+preview-off = Desligada
+tolerant-decoding = Decodificação tolerante
+tolerant-decoding-description = Ignora pacotes danificados e continua no próximo quadro-chave
+video-tracks = Faixas de vídeo
+video-tracks-description = Fluxos de vídeo do arquivo a extrair, o melhor é usado quando nenhum é marcado
+track-number = Faixa { $number }
+tracks-side-by-side = Faixas lado a lado
+tracks-side-by-side-description = Coloca as faixas selecionadas lado a lado em uma única folha de contato
+
+# Details dialog
+details = Detalhes
+export-json = Exportar JSON
+save = Salvar
+container = Contêiner
+format = Formato
+bit-rate = Taxa de bits
+stream = Fluxo
+codec = Codec
+profile = Perfil
+pixel-format = Formato de pixel
+resolution = Resolução
+rotation = Rotação
+field-order = Ordem dos campos
+video-track = Faixa de vídeo
+sample-rate = Taxa de amostragem
+channels = Canais
+color = Cor
+message-export-json-error = Falha ao exportar os detalhes para JSON.
+
+# Video card
+damaged = Danificado
+corrupt-packets = Pacotes corrompidos
+corrupt-frames = Quadros corrompidos
+decoding-failed = A decodificação parou em um erro
+discontinuities = Saltos de tempo
+
 # Errors
 generic-error = Um erro ocorreu:
 open-image-error = Falha ao abrir a imagem
 open-video-error = Falha ao abrir o vídeo
 invalid-directory = Diretório inválido!
 select-one-video = Selecione pelo menos um vídeo.
+capture-running = Uma captura já está em andamento.
+capture-interrupted = Captura interrompida, tentando novamente
+clip-exported = Trecho salvo em
+preview-saved = Prévia salva em
+timelapse-running = Um time-lapse já está em andamento.
+timelapse-saved = Time-lapse salvo em
 
 # About dialog
 translators = Hericson dos Santos <hericson.cipol@gmail.com>
@@ -18,6 +18,10 @@ use super::{
 
 use crate::app::models;
 use crate::fl;
+use core_vimpeg::video::service::{
+    CaptureOptions, ClipOptions, DecodeOptions, DecodeReport, PreviewOptions, SlitScanOptions,
+    SpriteOptions, StackOptions, TimelapseOptions,
+};
 
 pub struct ContentModel {
     video_list: AsyncController<VideoListModel>,
@@ -29,9 +33,35 @@ pub enum ContentInput {
     StartSearch(PathBuf),
     SearchCompleted(usize),
     FilterCount(usize),
-    ExtractVideosToImage(Vec<String>, models::LayoutType, bool, PathBuf),
-    ExtractFramesFromVideo(String, u32, u32, u32, bool, PathBuf),
+    ExtractVideosToImage(
+        Vec<String>,
+        models::LayoutType,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    ExtractFramesFromVideo(
+        String,
+        u32,
+        u32,
+        u32,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    StartCapture(String, PathBuf, CaptureOptions),
+    ExportClip(String, u32, u32, PathBuf, ClipOptions),
+    StackFrames(String, u32, u32, u32, PathBuf, StackOptions),
+    ExtractSprites(Vec<String>, bool, PathBuf, DecodeOptions, SpriteOptions),
+    ExtractHeatmaps(Vec<String>, u32, PathBuf, DecodeOptions),
+    StartTimelapse(String, PathBuf, TimelapseOptions),
+    ExtractSlitScan(String, PathBuf, SlitScanOptions),
     ImageCount(usize),
+    VideoDamaged(String, DecodeReport),
     Notify(String, u32),
 }
 
@@ -114,11 +144,17 @@ impl AsyncComponent for ContentModel {
                         layout_type,
                         show_timestamp,
                         dst_path,
+                        options,
+                        tracks,
+                        preview,
                     ) => ContentInput::ExtractVideosToImage(
                         video_list,
                         layout_type,
                         show_timestamp,
                         dst_path,
+                        options,
+                        tracks,
+                        preview,
                     ),
                     VideoListOutput::ExtractFramesFromVideo(
                         video_path,
@@ -127,6 +163,9 @@ impl AsyncComponent for ContentModel {
                         frame_rate,
                         show_timestamp,
                         dst_path,
+                        options,
+                        tracks,
+                        preview,
                     ) => ContentInput::ExtractFramesFromVideo(
                         video_path,
                         time_start,
@@ -134,7 +173,54 @@ impl AsyncComponent for ContentModel {
                         frame_rate,
                         show_timestamp,
                         dst_path,
+                        options,
+                        tracks,
+                        preview,
+                    ),
+                    VideoListOutput::StartCapture(video_path, dst_path, options) => {
+                        ContentInput::StartCapture(video_path, dst_path, options)
+                    }
+                    VideoListOutput::ExportClip(
+                        video_path,
+                        time_start,
+                        time_end,
+                        dst_path,
+                        options,
+                    ) => ContentInput::ExportClip(
+                        video_path, time_start, time_end, dst_path, options,
+                    ),
+                    VideoListOutput::StackFrames(
+                        video_path,
+                        time_start,
+                        time_end,
+                        frame_rate,
+                        dst_path,
+                        options,
+                    ) => ContentInput::StackFrames(
+                        video_path, time_start, time_end, frame_rate, dst_path, options,
                     ),
+                    VideoListOutput::ExtractSprites(
+                        video_list,
+                        show_timestamp,
+                        dst_path,
+                        options,
+                        sprite,
+                    ) => ContentInput::ExtractSprites(
+                        video_list,
+                        show_timestamp,
+                        dst_path,
+                        options,
+                        sprite,
+                    ),
+                    VideoListOutput::ExtractHeatmaps(video_list, interval, dst_path, options) => {
+                        ContentInput::ExtractHeatmaps(video_list, interval, dst_path, options)
+                    }
+                    VideoListOutput::StartTimelapse(video_path, dst_path, options) => {
+                        ContentInput::StartTimelapse(video_path, dst_path, options)
+                    }
+                    VideoListOutput::ExtractSlitScan(video_path, dst_path, options) => {
+                        ContentInput::ExtractSlitScan(video_path, dst_path, options)
+                    }
                     VideoListOutput::Notify(msg, timeout) => ContentInput::Notify(msg, timeout),
                 });
 
@@ -143,6 +229,9 @@ impl AsyncComponent for ContentModel {
                 .launch(())
                 .forward(sender.input_sender(), |output| match output {
                     ImageListOutput::ImageCount(count) => ContentInput::ImageCount(count),
+                    ImageListOutput::VideoDamaged(video_path, report) => {
+                        ContentInput::VideoDamaged(video_path, report)
+                    }
                     ImageListOutput::Notify(msg, timeout) => ContentInput::Notify(msg, timeout),
                 });
 
@@ -182,6 +271,9 @@ impl AsyncComponent for ContentModel {
                 layout_type,
                 show_timestamp,
                 dst_path,
+                options,
+                tracks,
+                preview,
             ) => {
                 widgets.stack.set_visible_child_name("image-page");
 
@@ -190,6 +282,9 @@ impl AsyncComponent for ContentModel {
                     layout_type,
                     show_timestamp,
                     dst_path,
+                    options,
+                    tracks,
+                    preview,
                 ));
             }
             ContentInput::ExtractFramesFromVideo(
@@ -199,6 +294,9 @@ impl AsyncComponent for ContentModel {
                 frame_rate,
                 show_timestamp,
                 dst_path,
+                options,
+                tracks,
+                preview,
             ) => {
                 widgets.stack.set_visible_child_name("image-page");
 
@@ -209,6 +307,66 @@ impl AsyncComponent for ContentModel {
                     frame_rate,
                     show_timestamp,
                     dst_path,
+                    options,
+                    tracks,
+                    preview,
+                ));
+            }
+            ContentInput::StartCapture(video_path, dst_path, options) => {
+                widgets.stack.set_visible_child_name("image-page");
+
+                self.image_list
+                    .emit(ImageListInput::StartCapture(video_path, dst_path, options));
+            }
+            ContentInput::ExportClip(video_path, time_start, time_end, dst_path, options) => {
+                self.image_list.emit(ImageListInput::ExportClip(
+                    video_path, time_start, time_end, dst_path, options,
+                ));
+            }
+            ContentInput::StackFrames(
+                video_path,
+                time_start,
+                time_end,
+                frame_rate,
+                dst_path,
+                options,
+            ) => {
+                widgets.stack.set_visible_child_name("image-page");
+
+                self.image_list.emit(ImageListInput::StackFrames(
+                    video_path, time_start, time_end, frame_rate, dst_path, options,
+                ));
+            }
+            ContentInput::ExtractSprites(video_list, show_timestamp, dst_path, options, sprite) => {
+                widgets.stack.set_visible_child_name("image-page");
+
+                self.image_list.emit(ImageListInput::ExtractSprites(
+                    video_list,
+                    show_timestamp,
+                    dst_path,
+                    options,
+                    sprite,
+                ));
+            }
+            ContentInput::ExtractHeatmaps(video_list, interval, dst_path, options) => {
+                widgets.stack.set_visible_child_name("image-page");
+
+                self.image_list.emit(ImageListInput::ExtractHeatmaps(
+                    video_list, interval, dst_path, options,
+                ));
+            }
+            ContentInput::StartTimelapse(video_path, dst_path, options) => {
+                widgets.stack.set_visible_child_name("image-page");
+
+                self.image_list.emit(ImageListInput::StartTimelapse(
+                    video_path, dst_path, options,
+                ));
+            }
+            ContentInput::ExtractSlitScan(video_path, dst_path, options) => {
+                widgets.stack.set_visible_child_name("image-page");
+
+                self.image_list.emit(ImageListInput::ExtractSlitScan(
+                    video_path, dst_path, options,
                 ));
             }
             ContentInput::ImageCount(count) => {
@@ -216,6 +374,10 @@ impl AsyncComponent for ContentModel {
                     .output(ContentOutput::ImageCount(count))
                     .unwrap_or_default();
             }
+            ContentInput::VideoDamaged(video_path, report) => {
+                self.video_list
+                    .emit(VideoListInput::MarkDamaged(video_path, report));
+            }
             ContentInput::Notify(msg, timeout) => {
                 sender
                     .output(ContentOutput::Notify(msg, timeout))
@@ -0,0 +1,292 @@
+use anyhow::Context;
+use std::{fs, path::PathBuf};
+
+use relm4::{
+    adw,
+    adw::prelude::{ActionRowExt, PreferencesGroupExt, PreferencesRowExt},
+    component::Controller,
+    gtk::{
+        self,
+        prelude::{BoxExt, ButtonExt, GtkWindowExt, OrientableExt, WidgetExt},
+    },
+    Component, ComponentController, ComponentParts, ComponentSender, RelmRemoveAllExt,
+    RelmWidgetExt,
+};
+use relm4_components::save_dialog::*;
+
+use crate::app::models;
+use crate::fl;
+use core_vimpeg::video::{MediaInfo, StreamInfo};
+
+pub struct DetailsDialogModel {
+    save_dialog: Controller<SaveDialog>,
+    video: Option<models::Video>,
+}
+
+#[derive(Debug)]
+pub enum DetailsDialogInput {
+    Show(models::Video),
+    Hide,
+    ExportRequest,
+    ExportResponse(PathBuf),
+    Ignore,
+}
+
+#[relm4::component(pub)]
+impl Component for DetailsDialogModel {
+    type Init = ();
+    type Input = DetailsDialogInput;
+    type Output = ();
+    type CommandOutput = ();
+
+    view! {
+        #[root]
+        adw::Window {
+            set_default_size: (500, 600),
+            set_hide_on_close: true,
+            set_modal: true,
+
+            adw::ToolbarView {
+                add_top_bar = &adw::HeaderBar {
+                    set_hexpand: true,
+                    set_show_start_title_buttons: true,
+                    set_show_end_title_buttons: true,
+
+                    pack_start = &gtk::Button {
+                        set_label: fl!("close"),
+                        connect_clicked => DetailsDialogInput::Hide,
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_label: fl!("export-json"),
+                        set_css_classes: &["suggested-action"],
+                        connect_clicked => DetailsDialogInput::ExportRequest,
+                    },
+
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: fl!("details"),
+                        #[watch]
+                        set_subtitle: match model.video.as_ref() {
+                            Some(video) => video.name.as_str(),
+                            None => "",
+                        },
+                    },
+                },
+
+                #[wrap(Some)]
+                set_content = &gtk::ScrolledWindow {
+                    set_hscrollbar_policy: gtk::PolicyType::Never,
+                    set_hexpand: true,
+                    set_vexpand: true,
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+
+                        #[name(banner)]
+                        adw::Banner {
+                            set_button_label: Some(fl!("close")),
+                            connect_button_clicked => |button| {
+                                button.set_revealed(false);
+                            },
+                        },
+
+                        #[name(details_box)]
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 12,
+                            set_margin_all: 12,
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let save_dialog_settings = SaveDialogSettings {
+            accept_label: String::from(fl!("save")),
+            cancel_label: String::from(fl!("cancel")),
+            create_folders: true,
+            is_modal: true,
+            filters: Vec::new(),
+        };
+
+        let save_dialog = SaveDialog::builder()
+            .transient_for_native(&root)
+            .launch(save_dialog_settings)
+            .forward(sender.input_sender(), |response| match response {
+                SaveDialogResponse::Accept(path) => DetailsDialogInput::ExportResponse(path),
+                SaveDialogResponse::Cancel => DetailsDialogInput::Ignore,
+            });
+
+        let model = DetailsDialogModel {
+            save_dialog,
+            video: None,
+        };
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            DetailsDialogInput::Show(video) => {
+                widgets.banner.set_revealed(false);
+                widgets.details_box.remove_all();
+                append_media_info(&widgets.details_box, &video.metadata);
+                self.video = Some(video);
+                root.present();
+            }
+            DetailsDialogInput::Hide => root.close(),
+            DetailsDialogInput::ExportRequest => {
+                if let Some(video) = self.video.as_ref() {
+                    let file_name = format!("{}.json", video.name);
+                    self.save_dialog.emit(SaveDialogMsg::SaveAs(file_name));
+                }
+            }
+            DetailsDialogInput::ExportResponse(path) => {
+                if let Some(video) = self.video.as_ref() {
+                    let result = video.metadata.to_json().and_then(|json| {
+                        fs::write(&path, json)
+                            .with_context(|| format!("failed to save {}", path.display()))
+                    });
+
+                    if let Err(err) = result {
+                        tracing::error!("{} {}", fl!("generic-error"), err);
+                        widgets.banner.set_title(fl!("message-export-json-error"));
+                        widgets.banner.set_revealed(true);
+                    }
+                }
+            }
+            DetailsDialogInput::Ignore => {}
+        }
+
+        self.update_view(widgets, sender);
+    }
+}
+
+fn append_media_info(details_box: &gtk::Box, media_info: &MediaInfo) {
+    let container_group = adw::PreferencesGroup::new();
+    container_group.set_title(fl!("container"));
+    append_row(
+        &container_group,
+        fl!("format"),
+        &media_info.format_description,
+    );
+    append_row(
+        &container_group,
+        fl!("duration"),
+        &format!("{:.3} s", media_info.duration),
+    );
+    append_row(
+        &container_group,
+        fl!("bit-rate"),
+        &format_bit_rate(media_info.bit_rate),
+    );
+    for (key, value) in media_info.tags.iter() {
+        append_row(&container_group, key, value);
+    }
+    details_box.append(&container_group);
+
+    for stream in media_info.streams.iter() {
+        details_box.append(&stream_group(stream));
+    }
+}
+
+fn stream_group(stream: &StreamInfo) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::new();
+    group.set_title(&format!(
+        "{} #{} ({})",
+        fl!("stream"),
+        stream.index,
+        stream.media_type
+    ));
+
+    append_row(&group, fl!("codec"), &stream.codec);
+    if let Some(profile) = stream.profile.as_ref() {
+        append_row(&group, fl!("profile"), profile);
+    }
+    if let Some(pixel_format) = stream.pixel_format.as_ref() {
+        append_row(&group, fl!("pixel-format"), pixel_format);
+    }
+    append_row(&group, fl!("bit-rate"), &format_bit_rate(stream.bit_rate));
+    if let Some(language) = stream.language.as_ref() {
+        append_row(&group, fl!("language"), language);
+    }
+    if stream.width > 0 && stream.height > 0 {
+        append_row(
+            &group,
+            fl!("resolution"),
+            &format!("{}x{}", stream.width, stream.height),
+        );
+        append_row(
+            &group,
+            fl!("frame-rate"),
+            &format!("{:.2} fps", stream.frame_rate),
+        );
+        append_row(&group, fl!("rotation"), &format!("{}°", stream.rotation));
+        if let Some(field_order) = stream.field_order.as_ref() {
+            append_row(&group, fl!("field-order"), field_order);
+        }
+        if let Some(track) = stream.track {
+            append_row(&group, fl!("video-track"), &(track + 1).to_string());
+        }
+    }
+    if stream.sample_rate > 0 {
+        append_row(
+            &group,
+            fl!("sample-rate"),
+            &format!("{} Hz", stream.sample_rate),
+        );
+        append_row(&group, fl!("channels"), &stream.channels.to_string());
+    }
+    if let Some(color) = stream.color.as_ref() {
+        let color_values = [
+            &color.range,
+            &color.space,
+            &color.primaries,
+            &color.transfer,
+        ]
+        .iter()
+        .map(|value| value.as_deref().unwrap_or("-"))
+        .collect::<Vec<&str>>()
+        .join(" / ");
+        append_row(&group, fl!("color"), &color_values);
+    }
+    for (key, value) in stream.tags.iter() {
+        append_row(&group, key, value);
+    }
+
+    group
+}
+
+fn append_row(group: &adw::PreferencesGroup, title: &str, value: &str) {
+    let row = adw::ActionRow::new();
+    // tags and file names may contain `&` or `<`
+    row.set_use_markup(false);
+    row.set_title(title);
+    row.set_subtitle(value);
+    row.set_subtitle_selectable(true);
+    row.add_css_class("property");
+    group.add(&row);
+}
+
+fn format_bit_rate(bit_rate: i64) -> String {
+    if bit_rate > 0 {
+        format!("{} kb/s", bit_rate / 1000)
+    } else {
+        String::from("-")
+    }
+}
@@ -7,8 +7,8 @@ use relm4::{
     gtk::{
         self,
         prelude::{
-            BoxExt, ButtonExt, EditableExt, EntryExt, FlowBoxChildExt, GtkWindowExt, OrientableExt,
-            WidgetExt,
+            BoxExt, ButtonExt, CheckButtonExt, EditableExt, EntryExt, FlowBoxChildExt,
+            GtkWindowExt, OrientableExt, WidgetExt,
         },
     },
     Component, ComponentController, ComponentParts, ComponentSender, RelmWidgetExt,
@@ -16,8 +16,15 @@ use relm4::{
 use relm4_components::open_dialog::*;
 use relm4_icons::icon_names;
 
-use crate::app::{factories::layout::LayoutModel, models, utils};
+use crate::app::{config::settings, factories::layout::LayoutModel, models, utils};
 use crate::fl;
+use core_vimpeg::video::{
+    service::{
+        CaptureOptions, ClipOptions, DecodeOptions, PreviewFormat, PreviewOptions, SlitOrientation,
+        SlitScanOptions, SpriteOptions, StackMode, StackOptions, TimelapseOptions,
+    },
+    Deinterlace, InputOptions, Orientation, ToneMap,
+};
 
 pub struct ExtractDialogModel {
     layout_list_factory: FactoryVecDeque<LayoutModel>,
@@ -25,6 +32,8 @@ pub struct ExtractDialogModel {
     layout_type: Option<models::LayoutType>,
     file_path: PathBuf,
     video: Option<models::Video>,
+    /// One check button per video track that can be selected.
+    track_buttons: Vec<gtk::CheckButton>,
 }
 
 #[derive(Debug)]
@@ -32,6 +41,7 @@ pub enum ExtractDialogInput {
     Show(ExtractDialogType),
     Hide,
     Extract,
+    ExportClip,
     SelectLayout(usize),
     OpenFileRequest,
     OpenFileResponse(PathBuf),
@@ -45,14 +55,38 @@ pub enum ExtractDialogOutput {
 
 #[derive(Debug)]
 pub enum ExtractDialogType {
-    Multi,
+    /// Several videos, with the largest number of video tracks among them.
+    Multi(usize),
     Single(models::Video),
 }
 
 #[derive(Debug)]
 pub enum ExtractDialogResponse {
-    ExtractToImage(models::LayoutType, bool, PathBuf),
-    ExtractFrames(u32, u32, u32, bool, PathBuf),
+    ExtractToImage(
+        models::LayoutType,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    ExtractFrames(
+        u32,
+        u32,
+        u32,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    Capture(PathBuf, CaptureOptions),
+    ExportClip(u32, u32, PathBuf, ClipOptions),
+    StackFrames(u32, u32, u32, PathBuf, StackOptions),
+    ExtractSprites(bool, PathBuf, DecodeOptions, SpriteOptions),
+    ExtractHeatmaps(u32, PathBuf, DecodeOptions),
+    Timelapse(PathBuf, TimelapseOptions),
+    SlitScan(PathBuf, SlitScanOptions),
 }
 
 #[relm4::component(pub)]
@@ -65,7 +99,7 @@ impl Component for ExtractDialogModel {
     view! {
         #[root]
         adw::Window {
-            set_default_size: (500, 415),
+            set_default_size: (500, 650),
             set_hide_on_close: true,
             set_modal: true,
             set_resizable: false,
@@ -369,154 +403,821 @@ impl Component for ExtractDialogModel {
                                             },
                                         },
                                     },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("stack-frames"),
+                                                set_tooltip_text: Some(fl!("stack-frames-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(drop_down_stack)]
+                                            gtk::DropDown {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_model: Some(&gtk::StringList::new(&[
+                                                    fl!("stack-off"),
+                                                    fl!("stack-mean"),
+                                                    fl!("stack-median"),
+                                                    fl!("stack-max-difference"),
+                                                ])),
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("register-frames"),
+                                                set_tooltip_text: Some(fl!("register-frames-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(register_frames)]
+                                            gtk::Switch {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_active: false,
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("precise-cut"),
+                                                set_tooltip_text: Some(fl!("precise-cut-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(precise_cut)]
+                                            gtk::Switch {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_active: false,
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("export-clip"),
+                                                set_tooltip_text: Some(fl!("export-clip-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            gtk::Button {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_icon_name: icon_names::ENCODE,
+                                                set_tooltip_text: Some(fl!("export-clip")),
+                                                connect_clicked => ExtractDialogInput::ExportClip,
+                                            },
+                                        },
+                                    },
                                 },
                             } -> {
                                 set_name: Some("extract-frames"),
                                 set_title: Some(fl!("extract-frames")),
                                 set_icon_name: Some(icon_names::VIDEO_REEL),
                             },
-                        },
-                    },
-                },
 
-                add_bottom_bar = &gtk::ListBox {
-                    set_margin_bottom: 6,
-                    set_margin_top: 3,
-                    set_margin_start: 6,
-                    set_margin_end: 6,
-                    set_selection_mode: gtk::SelectionMode::None,
-                    set_show_separators: false,
-                    set_css_classes: &["rich-list", "boxed-list"],
+                            add = &gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_margin_start: 6,
+                                set_margin_end: 6,
 
-                    gtk::ListBoxRow {
-                        gtk::Box {
-                            gtk::Label {
-                                set_label: "Show timestamp",
-                                set_xalign: 0f32,
-                                set_halign: gtk::Align::Start,
-                                set_valign: gtk::Align::Center,
-                                set_hexpand: true,
-                            },
+                                gtk::Label {
+                                    set_margin_all: 2,
 
-                            #[name(show_timestamp)]
-                            gtk::Switch {
-                                set_halign: gtk::Align::End,
-                                set_valign: gtk::Align::Center,
-                                set_active: true,
-                            },
-                        },
-                    },
+                                    #[watch]
+                                    set_label: match model.video.as_ref() {
+                                        Some(video) => video.name.as_str(),
+                                        None => "",
+                                    },
+                                },
 
-                    gtk::ListBoxRow {
-                        #[name(entry_dir)]
-                        gtk::Entry {
-                            #[watch]
-                            set_text?: &model.file_path.to_str(),
-                            set_margin_top: 6,
-                            set_margin_bottom: 6,
-                            set_hexpand: true,
-                            set_halign: gtk::Align::Fill,
-                            set_placeholder_text: Some(fl!("destination-directory")),
-                            set_secondary_icon_name: Some(icon_names::FOLDER_OPEN_FILLED),
-                            set_secondary_icon_tooltip_text: Some(fl!("select-directory")),
-                            connect_icon_release[sender] => move |_, icon_position| {
-                                if icon_position == gtk::EntryIconPosition::Secondary {
-                                    sender.input(ExtractDialogInput::OpenFileRequest);
-                                }
-                            },
-                        },
-                    },
-                },
+                                gtk::ListBox {
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                    set_show_separators: false,
+                                    set_css_classes: &["rich-list", "boxed-list"],
 
-            },
-        }
-    }
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("capture-interval"),
+                                                set_tooltip_text: Some(fl!("capture-interval-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
 
-    fn init(
-        _init: Self::Init,
-        root: Self::Root,
-        sender: ComponentSender<Self>,
-    ) -> ComponentParts<Self> {
-        let mut layout_list_factory = FactoryVecDeque::builder().launch_default().detach();
+                                            #[name(spin_interval)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(10f64, 1f64, 3600f64, 1f64, 10f64, 0f64),
+                                            },
+                                        },
+                                    },
 
-        {
-            let mut layout_guard = layout_list_factory.guard();
-            init_layout_list_factory(&mut layout_guard);
-        }
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("hourly-sheet"),
+                                                set_tooltip_text: Some(fl!("hourly-sheet-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
 
-        let open_dialog_settings = OpenDialogSettings {
-            folder_mode: true,
-            accept_label: String::from(fl!("open")),
-            cancel_label: String::from(fl!("cancel")),
-            create_folders: false,
-            is_modal: true,
-            filters: Vec::new(),
-        };
+                                            #[name(hourly_sheet)]
+                                            gtk::Switch {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_active: true,
+                                            },
+                                        },
+                                    },
+                                },
+                            } -> {
+                                set_name: Some("capture"),
+                                set_title: Some(fl!("capture")),
+                                set_icon_name: Some(icon_names::CAMERAS),
+                            },
 
-        let open_dialog = OpenDialog::builder()
-            .transient_for_native(&root)
-            .launch(open_dialog_settings)
-            .forward(sender.input_sender(), |response| match response {
-                OpenDialogResponse::Accept(path) => ExtractDialogInput::OpenFileResponse(path),
-                OpenDialogResponse::Cancel => ExtractDialogInput::Ignore,
-            });
+                            add = &gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_margin_top: 6,
+                                set_margin_start: 6,
+                                set_margin_end: 6,
 
-        let model = ExtractDialogModel {
-            layout_list_factory,
-            open_dialog,
-            layout_type: None,
-            file_path: PathBuf::default(),
-            video: None,
-        };
+                                gtk::ListBox {
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                    set_show_separators: false,
+                                    set_css_classes: &["rich-list", "boxed-list"],
 
-        let layout_grid_widget = model.layout_list_factory.widget();
-        let widgets = view_output!();
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("sprite-interval"),
+                                                set_tooltip_text: Some(fl!("sprite-interval-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
 
-        ComponentParts { model, widgets }
-    }
+                                            #[name(spin_sprite_interval)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(10f64, 1f64, 3600f64, 1f64, 10f64, 0f64),
+                                            },
+                                        },
+                                    },
 
-    fn update_with_view(
-        &mut self,
-        widgets: &mut Self::Widgets,
-        message: Self::Input,
-        sender: ComponentSender<Self>,
-        root: &Self::Root,
-    ) {
-        match message {
-            ExtractDialogInput::Show(dialog_type) => {
-                let mut is_single = false;
-                match dialog_type {
-                    ExtractDialogType::Multi => {
-                        self.video = None;
-                    }
-                    ExtractDialogType::Single(video) => {
-                        let spin_rate = &widgets.spin_rate;
-                        spin_rate.set_adjustment(&gtk::Adjustment::new(
-                            video.rate, 1f64, video.rate, 1f64, 1f64, 0f64,
-                        ));
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("sprite-tile-size"),
+                                                set_tooltip_text: Some(fl!("sprite-tile-size-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
 
-                        self.video = Some(video);
-                        is_single = true;
-                    }
-                };
+                                            #[name(spin_sprite_tile_size)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(160f64, 64f64, 640f64, 16f64, 64f64, 0f64),
+                                            },
+                                        },
+                                    },
 
-                if let Some(page) = widgets.stack.child_by_name("extract-frames") {
-                    widgets.stack.page(&page).set_visible(is_single);
-                }
-                widgets.stack.set_visible_child_name("extract-to-image");
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("sprite-columns"),
+                                                set_tooltip_text: Some(fl!("sprite-columns-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_sprite_columns)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(10f64, 1f64, 50f64, 1f64, 5f64, 0f64),
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("sprite-rows"),
+                                                set_tooltip_text: Some(fl!("sprite-rows-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_sprite_rows)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(10f64, 1f64, 50f64, 1f64, 5f64, 0f64),
+                                            },
+                                        },
+                                    },
+                                },
+                            } -> {
+                                set_name: Some("sprites"),
+                                set_title: Some(fl!("sprites")),
+                                set_icon_name: Some(icon_names::TABS_STACK),
+                            },
+
+                            add = &gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_margin_top: 6,
+                                set_margin_start: 6,
+                                set_margin_end: 6,
+
+                                gtk::ListBox {
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                    set_show_separators: false,
+                                    set_css_classes: &["rich-list", "boxed-list"],
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("heatmap-interval"),
+                                                set_tooltip_text: Some(fl!("heatmap-interval-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_heatmap_interval)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(1f64, 1f64, 600f64, 1f64, 10f64, 0f64),
+                                            },
+                                        },
+                                    },
+                                },
+                            } -> {
+                                set_name: Some("heatmap"),
+                                set_title: Some(fl!("heatmap")),
+                                set_icon_name: Some(icon_names::LOUPE_LARGE),
+                            },
+
+                            add = &gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_margin_start: 6,
+                                set_margin_end: 6,
+
+                                gtk::Label {
+                                    set_margin_all: 2,
+
+                                    #[watch]
+                                    set_label: match model.video.as_ref() {
+                                        Some(video) => video.name.as_str(),
+                                        None => "",
+                                    },
+                                },
+
+                                gtk::ListBox {
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                    set_show_separators: false,
+                                    set_css_classes: &["rich-list", "boxed-list"],
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("timelapse-interval"),
+                                                set_tooltip_text: Some(fl!("timelapse-interval-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_timelapse_interval)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(10f64, 1f64, 3600f64, 1f64, 10f64, 0f64),
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("timelapse-frame-rate"),
+                                                set_tooltip_text: Some(fl!("timelapse-frame-rate-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_timelapse_frame_rate)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(25f64, 1f64, 60f64, 1f64, 5f64, 0f64),
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("motion-only"),
+                                                set_tooltip_text: Some(fl!("motion-only-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(motion_only)]
+                                            gtk::Switch {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_active: false,
+                                            },
+                                        },
+                                    },
+                                },
+                            } -> {
+                                set_name: Some("timelapse"),
+                                set_title: Some(fl!("timelapse")),
+                                set_icon_name: Some(icon_names::PLAY_LARGE),
+                            },
+
+                            add = &gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_margin_start: 6,
+                                set_margin_end: 6,
+
+                                gtk::Label {
+                                    set_margin_all: 2,
+
+                                    #[watch]
+                                    set_label: match model.video.as_ref() {
+                                        Some(video) => video.name.as_str(),
+                                        None => "",
+                                    },
+                                },
+
+                                gtk::ListBox {
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                    set_show_separators: false,
+                                    set_css_classes: &["rich-list", "boxed-list"],
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("slit-orientation"),
+                                                set_tooltip_text: Some(fl!("slit-orientation-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(drop_down_slit_orientation)]
+                                            gtk::DropDown {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_model: Some(&gtk::StringList::new(&[
+                                                    fl!("slit-column"),
+                                                    fl!("slit-row"),
+                                                ])),
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("slit-position"),
+                                                set_tooltip_text: Some(fl!("slit-position-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_slit_position)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(50f64, 0f64, 100f64, 1f64, 10f64, 0f64),
+                                            },
+                                        },
+                                    },
+
+                                    gtk::ListBoxRow {
+                                        gtk::Box {
+                                            gtk::Label {
+                                                set_label: fl!("slit-frame-rate"),
+                                                set_tooltip_text: Some(fl!("slit-frame-rate-description")),
+                                                set_xalign: 0f32,
+                                                set_halign: gtk::Align::Start,
+                                                set_valign: gtk::Align::Center,
+                                                set_hexpand: true,
+                                            },
+
+                                            #[name(spin_slit_frame_rate)]
+                                            gtk::SpinButton {
+                                                set_halign: gtk::Align::End,
+                                                set_valign: gtk::Align::Center,
+                                                set_adjustment: &gtk::Adjustment::new(1f64, 1f64, 25f64, 1f64, 5f64, 0f64),
+                                            },
+                                        },
+                                    },
+                                },
+                            } -> {
+                                set_name: Some("slit-scan"),
+                                set_title: Some(fl!("slit-scan")),
+                                set_icon_name: Some(icon_names::PENCIL_AND_PAPER),
+                            },
+                        },
+                    },
+                },
+
+                add_bottom_bar = &gtk::ListBox {
+                    set_margin_bottom: 6,
+                    set_margin_top: 3,
+                    set_margin_start: 6,
+                    set_margin_end: 6,
+                    set_selection_mode: gtk::SelectionMode::None,
+                    set_show_separators: false,
+                    set_css_classes: &["rich-list", "boxed-list"],
+
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("rotation"),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(drop_down_orientation)]
+                            gtk::DropDown {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_model: Some(&gtk::StringList::new(&[
+                                    fl!("rotation-auto"),
+                                    "0°",
+                                    "90°",
+                                    "180°",
+                                    "270°",
+                                    fl!("flip-horizontal"),
+                                    fl!("flip-vertical"),
+                                ])),
+                            },
+                        },
+                    },
+
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("deinterlace"),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(drop_down_deinterlace)]
+                            gtk::DropDown {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_model: Some(&gtk::StringList::new(&[
+                                    fl!("deinterlace-off"),
+                                    fl!("deinterlace-fields"),
+                                    fl!("deinterlace-bob"),
+                                    fl!("deinterlace-quality"),
+                                ])),
+                            },
+                        },
+                    },
+
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("tone-mapping"),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(drop_down_tone_map)]
+                            gtk::DropDown {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_model: Some(&gtk::StringList::new(&[
+                                    fl!("tone-map-off"),
+                                    fl!("tone-map-clip"),
+                                    "Reinhard",
+                                    "Hable",
+                                    "Möbius",
+                                ])),
+                                set_selected: 3,
+                            },
+                        },
+                    },
+
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: "Show timestamp",
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(show_timestamp)]
+                            gtk::Switch {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_active: true,
+                            },
+                        },
+                    },
+
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("animated-preview"),
+                                set_tooltip_text: Some(fl!("animated-preview-description")),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(drop_down_preview)]
+                            gtk::DropDown {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_model: Some(&gtk::StringList::new(&[
+                                    fl!("preview-off"),
+                                    "GIF",
+                                    "WebP",
+                                    "MP4",
+                                ])),
+                            },
+                        },
+                    },
+
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("tolerant-decoding"),
+                                set_tooltip_text: Some(fl!("tolerant-decoding-description")),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(tolerant_decoding)]
+                            gtk::Switch {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_active: false,
+                            },
+                        },
+                    },
+
+                    #[name(tracks_row)]
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("video-tracks"),
+                                set_tooltip_text: Some(fl!("video-tracks-description")),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(tracks_box)]
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_spacing: 3,
+                            },
+                        },
+                    },
+
+                    #[name(tracks_side_by_side_row)]
+                    gtk::ListBoxRow {
+                        gtk::Box {
+                            gtk::Label {
+                                set_label: fl!("tracks-side-by-side"),
+                                set_tooltip_text: Some(fl!("tracks-side-by-side-description")),
+                                set_xalign: 0f32,
+                                set_halign: gtk::Align::Start,
+                                set_valign: gtk::Align::Center,
+                                set_hexpand: true,
+                            },
+
+                            #[name(tracks_side_by_side)]
+                            gtk::Switch {
+                                set_halign: gtk::Align::End,
+                                set_valign: gtk::Align::Center,
+                                set_active: false,
+                            },
+                        },
+                    },
+
+                    gtk::ListBoxRow {
+                        #[name(entry_dir)]
+                        gtk::Entry {
+                            #[watch]
+                            set_text?: &model.file_path.to_str(),
+                            set_margin_top: 6,
+                            set_margin_bottom: 6,
+                            set_hexpand: true,
+                            set_halign: gtk::Align::Fill,
+                            set_placeholder_text: Some(fl!("destination-directory")),
+                            set_secondary_icon_name: Some(icon_names::FOLDER_OPEN_FILLED),
+                            set_secondary_icon_tooltip_text: Some(fl!("select-directory")),
+                            connect_icon_release[sender] => move |_, icon_position| {
+                                if icon_position == gtk::EntryIconPosition::Secondary {
+                                    sender.input(ExtractDialogInput::OpenFileRequest);
+                                }
+                            },
+                        },
+                    },
+                },
+
+            },
+        }
+    }
+
+    fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let mut layout_list_factory = FactoryVecDeque::builder().launch_default().detach();
+
+        {
+            let mut layout_guard = layout_list_factory.guard();
+            init_layout_list_factory(&mut layout_guard);
+        }
+
+        let open_dialog_settings = OpenDialogSettings {
+            folder_mode: true,
+            accept_label: String::from(fl!("open")),
+            cancel_label: String::from(fl!("cancel")),
+            create_folders: false,
+            is_modal: true,
+            filters: Vec::new(),
+        };
+
+        let open_dialog = OpenDialog::builder()
+            .transient_for_native(&root)
+            .launch(open_dialog_settings)
+            .forward(sender.input_sender(), |response| match response {
+                OpenDialogResponse::Accept(path) => ExtractDialogInput::OpenFileResponse(path),
+                OpenDialogResponse::Cancel => ExtractDialogInput::Ignore,
+            });
+
+        let model = ExtractDialogModel {
+            layout_list_factory,
+            open_dialog,
+            layout_type: None,
+            file_path: PathBuf::default(),
+            video: None,
+            track_buttons: Vec::new(),
+        };
+
+        let layout_grid_widget = model.layout_list_factory.widget();
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            ExtractDialogInput::Show(dialog_type) => {
+                let mut is_single = false;
+                let track_labels: Vec<String> = match &dialog_type {
+                    ExtractDialogType::Multi(tracks) => (0..*tracks)
+                        .map(|track| fl!("track-number", number = track + 1).to_string())
+                        .collect(),
+                    ExtractDialogType::Single(video) => video
+                        .metadata
+                        .video_tracks()
+                        .enumerate()
+                        .map(|(track, stream)| {
+                            format!(
+                                "{} · {} {}x{}",
+                                fl!("track-number", number = track + 1),
+                                stream.codec,
+                                stream.width,
+                                stream.height
+                            )
+                        })
+                        .collect(),
+                };
+                self.set_track_buttons(widgets, &track_labels);
+
+                match dialog_type {
+                    ExtractDialogType::Multi(_) => {
+                        self.video = None;
+                    }
+                    ExtractDialogType::Single(video) => {
+                        let spin_rate = &widgets.spin_rate;
+                        spin_rate.set_adjustment(&gtk::Adjustment::new(
+                            video.rate, 1f64, video.rate, 1f64, 1f64, 0f64,
+                        ));
+
+                        self.video = Some(video);
+                        is_single = true;
+                    }
+                };
+
+                let deinterlace = settings::get_settings()
+                    .map(|settings_toml| settings_toml.deinterlace)
+                    .unwrap_or(Deinterlace::Off);
+                widgets
+                    .drop_down_deinterlace
+                    .set_selected(match deinterlace {
+                        Deinterlace::Off => 0,
+                        Deinterlace::Fields => 1,
+                        Deinterlace::Bob => 2,
+                        Deinterlace::Quality => 3,
+                    });
+
+                for page_name in ["extract-frames", "capture", "timelapse", "slit-scan"] {
+                    if let Some(page) = widgets.stack.child_by_name(page_name) {
+                        widgets.stack.page(&page).set_visible(is_single);
+                    }
+                }
+                widgets.stack.set_visible_child_name("extract-to-image");
                 self.clear_errors(widgets);
                 root.present();
             }
             ExtractDialogInput::Hide => root.close(),
             ExtractDialogInput::Extract => {
                 if let Some(child_name) = widgets.stack.visible_child_name() {
-                    if child_name.as_str() == "extract-frames" {
-                        self.extract_frames(widgets, sender.clone());
-                    } else {
-                        self.extract_to_image(widgets, sender.clone());
+                    match child_name.as_str() {
+                        "extract-frames" => self.extract_frames(widgets, sender.clone()),
+                        "capture" => self.capture(widgets, sender.clone()),
+                        "sprites" => self.extract_sprites(widgets, sender.clone()),
+                        "heatmap" => self.extract_heatmaps(widgets, sender.clone()),
+                        "timelapse" => self.timelapse(widgets, sender.clone()),
+                        "slit-scan" => self.slit_scan(widgets, sender.clone()),
+                        _ => self.extract_to_image(widgets, sender.clone()),
                     }
                 }
             }
+            ExtractDialogInput::ExportClip => self.export_clip(widgets, sender.clone()),
             ExtractDialogInput::SelectLayout(index) => {
                 let layouts_guard = self.layout_list_factory.guard();
                 if let Some(layout_model) = layouts_guard.get(index) {
@@ -597,6 +1298,20 @@ fn init_layout_list_factory(layout_guard: &mut FactoryVecDequeGuard<LayoutModel>
     ));
 }
 
+/// Seconds of an `HH:MM:SS` entry, 0 when it is incomplete.
+fn entry_seconds(entry: &gtk::Entry) -> u32 {
+    let time = entry
+        .text()
+        .split(':')
+        .map(|v| v.parse::<u32>().unwrap_or(0))
+        .collect::<Vec<u32>>();
+    if time.len() == 3 {
+        time[0] * 3600 + time[1] * 60 + time[2]
+    } else {
+        0
+    }
+}
+
 impl ExtractDialogModel {
     fn clear_errors(&self, widgets: &mut ExtractDialogModelWidgets) {
         widgets
@@ -627,12 +1342,21 @@ impl ExtractDialogModel {
             banner.set_revealed(false);
         }
 
+        let track_selection = self.track_selection(widgets);
+
         if let Some(layout_type) = self.layout_type {
             let show_timestamp = widgets.show_timestamp.state();
             let file_path = self.file_path.clone();
             sender
                 .output(ExtractDialogOutput::Response(
-                    ExtractDialogResponse::ExtractToImage(layout_type, show_timestamp, file_path),
+                    ExtractDialogResponse::ExtractToImage(
+                        layout_type,
+                        show_timestamp,
+                        file_path,
+                        self.decode_options(widgets),
+                        track_selection,
+                        self.preview_options(widgets),
+                    ),
                 ))
                 .unwrap_or_default();
             sender.input(ExtractDialogInput::Hide);
@@ -647,28 +1371,8 @@ impl ExtractDialogModel {
         let banner = &widgets.banner;
 
         let entry_time_start = &widgets.entry_time_start;
-        let time_start = entry_time_start
-            .text()
-            .split(':')
-            .map(|v| v.parse::<u32>().unwrap_or(0))
-            .collect::<Vec<u32>>();
-        let time_start = if time_start.len() == 3 {
-            time_start[0] * 3600 + time_start[1] * 60 + time_start[2]
-        } else {
-            0
-        };
-
-        let entry_time_end = &widgets.entry_time_end;
-        let time_end = entry_time_end
-            .text()
-            .split(':')
-            .map(|v| v.parse::<u32>().unwrap_or(0))
-            .collect::<Vec<u32>>();
-        let time_end = if time_end.len() == 3 {
-            time_end[0] * 3600 + time_end[1] * 60 + time_end[2]
-        } else {
-            0
-        };
+        let time_start = entry_seconds(entry_time_start);
+        let time_end = entry_seconds(&widgets.entry_time_end);
 
         if time_start >= time_end {
             entry_time_start.set_class_active("input-error", true);
@@ -703,6 +1407,33 @@ impl ExtractDialogModel {
             banner.set_revealed(false);
         }
 
+        let track_selection = self.track_selection(widgets);
+
+        if let Some(mode) = self.stack_mode(widgets) {
+            let options = StackOptions {
+                mode,
+                register: widgets.register_frames.state(),
+                decode: DecodeOptions {
+                    // a single still is made, from the first track
+                    video_track: track_selection.tracks.first().copied(),
+                    ..self.decode_options(widgets)
+                },
+            };
+            sender
+                .output(ExtractDialogOutput::Response(
+                    ExtractDialogResponse::StackFrames(
+                        time_start,
+                        time_end,
+                        frame_rate,
+                        self.file_path.clone(),
+                        options,
+                    ),
+                ))
+                .unwrap_or_default();
+            sender.input(ExtractDialogInput::Hide);
+            return;
+        }
+
         let show_timestamp = widgets.show_timestamp.state();
         let file_path = self.file_path.clone();
         sender
@@ -713,9 +1444,344 @@ impl ExtractDialogModel {
                     frame_rate,
                     show_timestamp,
                     file_path,
+                    self.decode_options(widgets),
+                    track_selection,
+                    self.preview_options(widgets),
                 ),
             ))
             .unwrap_or_default();
         sender.input(ExtractDialogInput::Hide);
     }
+
+    fn export_clip(&self, widgets: &mut ExtractDialogModelWidgets, sender: ComponentSender<Self>) {
+        let banner = &widgets.banner;
+
+        let entry_time_start = &widgets.entry_time_start;
+        let time_start = entry_seconds(entry_time_start);
+        let time_end = entry_seconds(&widgets.entry_time_end);
+        if time_start >= time_end {
+            entry_time_start.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-duration"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_time_start.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let entry_dir = &widgets.entry_dir;
+        if !self.file_path.exists() {
+            entry_dir.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-dst-dir"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_dir.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let track_selection = self.track_selection(widgets);
+
+        let options = ClipOptions {
+            precise: widgets.precise_cut.state(),
+            decode: DecodeOptions {
+                // the cut follows a single track, every stream is copied
+                video_track: track_selection.tracks.first().copied(),
+                ..self.decode_options(widgets)
+            },
+        };
+        let file_path = self.file_path.clone();
+        sender
+            .output(ExtractDialogOutput::Response(
+                ExtractDialogResponse::ExportClip(time_start, time_end, file_path, options),
+            ))
+            .unwrap_or_default();
+        sender.input(ExtractDialogInput::Hide);
+    }
+
+    fn capture(&self, widgets: &mut ExtractDialogModelWidgets, sender: ComponentSender<Self>) {
+        let banner = &widgets.banner;
+
+        let entry_dir = &widgets.entry_dir;
+        if !self.file_path.exists() {
+            entry_dir.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-dst-dir"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_dir.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let track_selection = self.track_selection(widgets);
+
+        let options = CaptureOptions {
+            interval: widgets.spin_interval.value() as u32,
+            show_timestamp: widgets.show_timestamp.state(),
+            hourly_sheet: widgets.hourly_sheet.state(),
+            decode: DecodeOptions {
+                // a capture follows a single track
+                video_track: track_selection.tracks.first().copied(),
+                ..self.decode_options(widgets)
+            },
+        };
+        let file_path = self.file_path.clone();
+        sender
+            .output(ExtractDialogOutput::Response(
+                ExtractDialogResponse::Capture(file_path, options),
+            ))
+            .unwrap_or_default();
+        sender.input(ExtractDialogInput::Hide);
+    }
+
+    fn extract_sprites(
+        &self,
+        widgets: &mut ExtractDialogModelWidgets,
+        sender: ComponentSender<Self>,
+    ) {
+        let banner = &widgets.banner;
+
+        let entry_dir = &widgets.entry_dir;
+        if !self.file_path.exists() {
+            entry_dir.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-dst-dir"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_dir.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let track_selection = self.track_selection(widgets);
+
+        let sprite = SpriteOptions {
+            interval: widgets.spin_sprite_interval.value() as u32,
+            tile_size: widgets.spin_sprite_tile_size.value() as u32,
+            columns: widgets.spin_sprite_columns.value() as usize,
+            rows: widgets.spin_sprite_rows.value() as usize,
+        };
+        let options = DecodeOptions {
+            // the thumbnails follow a single track
+            video_track: track_selection.tracks.first().copied(),
+            ..self.decode_options(widgets)
+        };
+        let show_timestamp = widgets.show_timestamp.state();
+        let file_path = self.file_path.clone();
+        sender
+            .output(ExtractDialogOutput::Response(
+                ExtractDialogResponse::ExtractSprites(show_timestamp, file_path, options, sprite),
+            ))
+            .unwrap_or_default();
+        sender.input(ExtractDialogInput::Hide);
+    }
+
+    fn extract_heatmaps(
+        &self,
+        widgets: &mut ExtractDialogModelWidgets,
+        sender: ComponentSender<Self>,
+    ) {
+        let banner = &widgets.banner;
+
+        let entry_dir = &widgets.entry_dir;
+        if !self.file_path.exists() {
+            entry_dir.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-dst-dir"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_dir.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let track_selection = self.track_selection(widgets);
+
+        let interval = widgets.spin_heatmap_interval.value() as u32;
+        let options = DecodeOptions {
+            // the heatmap follows a single track
+            video_track: track_selection.tracks.first().copied(),
+            ..self.decode_options(widgets)
+        };
+        let file_path = self.file_path.clone();
+        sender
+            .output(ExtractDialogOutput::Response(
+                ExtractDialogResponse::ExtractHeatmaps(interval, file_path, options),
+            ))
+            .unwrap_or_default();
+        sender.input(ExtractDialogInput::Hide);
+    }
+
+    fn timelapse(&self, widgets: &mut ExtractDialogModelWidgets, sender: ComponentSender<Self>) {
+        let banner = &widgets.banner;
+
+        let entry_dir = &widgets.entry_dir;
+        if !self.file_path.exists() {
+            entry_dir.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-dst-dir"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_dir.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let track_selection = self.track_selection(widgets);
+
+        let options = TimelapseOptions {
+            interval: widgets.spin_timelapse_interval.value() as u32,
+            motion_only: widgets.motion_only.state(),
+            frame_rate: widgets.spin_timelapse_frame_rate.value() as u32,
+            show_timestamp: widgets.show_timestamp.state(),
+            decode: DecodeOptions {
+                // a time-lapse follows a single track
+                video_track: track_selection.tracks.first().copied(),
+                ..self.decode_options(widgets)
+            },
+        };
+        let file_path = self.file_path.clone();
+        sender
+            .output(ExtractDialogOutput::Response(
+                ExtractDialogResponse::Timelapse(file_path, options),
+            ))
+            .unwrap_or_default();
+        sender.input(ExtractDialogInput::Hide);
+    }
+
+    fn slit_scan(&self, widgets: &mut ExtractDialogModelWidgets, sender: ComponentSender<Self>) {
+        let banner = &widgets.banner;
+
+        let entry_dir = &widgets.entry_dir;
+        if !self.file_path.exists() {
+            entry_dir.set_class_active("input-error", true);
+            banner.set_title(fl!("message-invalid-dst-dir"));
+            banner.set_revealed(true);
+            return;
+        } else {
+            entry_dir.set_class_active("input-error", false);
+            banner.set_revealed(false);
+        }
+
+        let track_selection = self.track_selection(widgets);
+
+        let orientation = match widgets.drop_down_slit_orientation.selected() {
+            1 => SlitOrientation::Horizontal,
+            _ => SlitOrientation::Vertical,
+        };
+        let options = SlitScanOptions {
+            orientation,
+            position: widgets.spin_slit_position.value() / 100.0,
+            frame_rate: widgets.spin_slit_frame_rate.value() as u32,
+            decode: DecodeOptions {
+                // the slit is read from a single track
+                video_track: track_selection.tracks.first().copied(),
+                ..self.decode_options(widgets)
+            },
+        };
+        let file_path = self.file_path.clone();
+        sender
+            .output(ExtractDialogOutput::Response(
+                ExtractDialogResponse::SlitScan(file_path, options),
+            ))
+            .unwrap_or_default();
+        sender.input(ExtractDialogInput::Hide);
+    }
+
+    /// Replaces the track check buttons, the track rows are only shown when
+    /// there is more than one track to pick from.
+    fn set_track_buttons(&mut self, widgets: &mut ExtractDialogModelWidgets, labels: &[String]) {
+        for button in self.track_buttons.drain(..) {
+            widgets.tracks_box.remove(&button);
+        }
+        for label in labels {
+            let button = gtk::CheckButton::with_label(label);
+            widgets.tracks_box.append(&button);
+            self.track_buttons.push(button);
+        }
+
+        let has_tracks = labels.len() > 1;
+        widgets.tracks_row.set_visible(has_tracks);
+        widgets.tracks_side_by_side_row.set_visible(has_tracks);
+    }
+
+    fn track_selection(&self, widgets: &ExtractDialogModelWidgets) -> models::TrackSelection {
+        let tracks = self
+            .track_buttons
+            .iter()
+            .enumerate()
+            .filter(|(_, button)| button.is_active())
+            .map(|(track, _)| track)
+            .collect();
+
+        models::TrackSelection {
+            tracks,
+            side_by_side: widgets.tracks_side_by_side.state(),
+        }
+    }
+
+    fn preview_options(&self, widgets: &ExtractDialogModelWidgets) -> Option<PreviewOptions> {
+        let format = match widgets.drop_down_preview.selected() {
+            1 => PreviewFormat::Gif,
+            2 => PreviewFormat::Webp,
+            3 => PreviewFormat::Mp4,
+            _ => return None,
+        };
+
+        let preview = settings::get_settings()
+            .map(|settings_toml| settings_toml.preview)
+            .unwrap_or_default();
+
+        Some(PreviewOptions { format, ..preview })
+    }
+
+    fn stack_mode(&self, widgets: &ExtractDialogModelWidgets) -> Option<StackMode> {
+        match widgets.drop_down_stack.selected() {
+            1 => Some(StackMode::Mean),
+            2 => Some(StackMode::Median),
+            3 => Some(StackMode::MaxDifference),
+            _ => None,
+        }
+    }
+
+    fn decode_options(&self, widgets: &ExtractDialogModelWidgets) -> DecodeOptions {
+        let orientation = match widgets.drop_down_orientation.selected() {
+            1 => Some(Orientation::new(0, false, false)),
+            2 => Some(Orientation::new(90, false, false)),
+            3 => Some(Orientation::new(180, false, false)),
+            4 => Some(Orientation::new(270, false, false)),
+            5 => Some(Orientation::new(0, true, false)),
+            6 => Some(Orientation::new(0, false, true)),
+            _ => None,
+        };
+
+        let deinterlace = match widgets.drop_down_deinterlace.selected() {
+            1 => Deinterlace::Fields,
+            2 => Deinterlace::Bob,
+            3 => Deinterlace::Quality,
+            _ => Deinterlace::Off,
+        };
+
+        let tone_map = match widgets.drop_down_tone_map.selected() {
+            0 => ToneMap::Off,
+            1 => ToneMap::Clip,
+            2 => ToneMap::Reinhard,
+            4 => ToneMap::Mobius,
+            _ => ToneMap::Hable,
+        };
+
+        let (threads, input) = settings::get_settings()
+            .map(|settings_toml| (settings_toml.decoder_threads, settings_toml.input))
+            .unwrap_or((
+                models::preference::DEFAULT_DECODER_THREADS,
+                InputOptions::default(),
+            ));
+
+        DecodeOptions {
+            orientation,
+            deinterlace,
+            tone_map,
+            tolerant: widgets.tolerant_decoding.state(),
+            threads,
+            input,
+            ..Default::default()
+        }
+    }
 }
@@ -1,5 +1,10 @@
 use anyhow::Result;
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 use relm4::{
     component::{AsyncComponent, AsyncComponentParts, AsyncComponentSender},
@@ -11,17 +16,32 @@ use relm4::{
 use relm4_icons::icon_names;
 
 use crate::app::{
+    config::settings,
     factories::image::{ImageInput, ImageModel},
     models,
 };
 use crate::fl;
-use core_vimpeg::video::service;
+use core_vimpeg::video::{
+    media_name,
+    service::{
+        self, CaptureEvent, CaptureOptions, CaptureResult, ClipOptions, ClipResult, DecodeError,
+        DecodeOptions, DecodeReport, PreviewFormat, PreviewOptions, SlitScanOptions, SpriteOptions,
+        StackOptions, TimelapseOptions, TimelapseResult,
+    },
+};
 
 pub struct ImageListModel {
     image_list_factory: AsyncFactoryVecDeque<ImageModel>,
     total_videos: usize,
     processed_videos: usize,
     thumbnail_size: i32,
+    pending_videos: VecDeque<VideoJob>,
+    running_videos: usize,
+    parallel_videos: usize,
+    /// Set to stop the running snapshot capture.
+    capture_stop: Option<Arc<AtomicBool>>,
+    /// Set to stop the running time-lapse export.
+    timelapse_stop: Option<Arc<AtomicBool>>,
 }
 
 impl ImageListModel {
@@ -31,17 +51,93 @@ impl ImageListModel {
             total_videos: 0,
             processed_videos: 0,
             thumbnail_size: models::image::THUMBNAIL_SIZE,
+            pending_videos: VecDeque::new(),
+            running_videos: 0,
+            parallel_videos: models::preference::automatic_parallel_videos(),
+            capture_stop: None,
+            timelapse_stop: None,
         }
     }
 }
 
+/// A video waiting for a free extraction slot.
+#[derive(Debug)]
+struct VideoJob {
+    video_path: String,
+    image_path: String,
+    cols: usize,
+    rows: usize,
+    show_timestamp: bool,
+    options: DecodeOptions,
+    /// Tracks put side by side in the tiles, empty for a single track.
+    tracks: Vec<usize>,
+    output: JobOutput,
+}
+
+/// What a queued video is extracted into.
+#[derive(Debug, Clone, Copy)]
+enum JobOutput {
+    ContactSheet,
+    /// The frames of the contact sheet as an animation.
+    Preview(PreviewOptions),
+    /// Sprite sheets and a WebVTT track in the `image_path` directory.
+    Sprites(SpriteOptions),
+    /// Motion heatmap of frames sampled every given seconds.
+    Heatmap(u32),
+    /// Frames of a time range saved one by one in the `image_path`
+    /// directory, or as an animation.
+    Frames {
+        time_start: u32,
+        time_end: u32,
+        frame_rate: u32,
+        preview: Option<PreviewOptions>,
+    },
+    /// Frames of a time range combined into a single still.
+    Stack {
+        time_start: u32,
+        time_end: u32,
+        frame_rate: u32,
+        options: StackOptions,
+    },
+    /// Slit-scan strips in the `image_path` directory.
+    SlitScan(SlitScanOptions),
+}
+
 #[derive(Debug)]
 pub enum ImageListInput {
     ZoomIn,
     ZoomOut,
     ClearImageList,
-    ExtractVideosToImage(Vec<String>, models::LayoutType, bool, PathBuf),
-    ExtractFramesFromVideo(String, u32, u32, u32, bool, PathBuf),
+    ExtractVideosToImage(
+        Vec<String>,
+        models::LayoutType,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    ExtractFramesFromVideo(
+        String,
+        u32,
+        u32,
+        u32,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    StartCapture(String, PathBuf, CaptureOptions),
+    CaptureProgress(CaptureEvent),
+    StopCapture,
+    ExportClip(String, u32, u32, PathBuf, ClipOptions),
+    StackFrames(String, u32, u32, u32, PathBuf, StackOptions),
+    ExtractSprites(Vec<String>, bool, PathBuf, DecodeOptions, SpriteOptions),
+    ExtractHeatmaps(Vec<String>, u32, PathBuf, DecodeOptions),
+    StartTimelapse(String, PathBuf, TimelapseOptions),
+    StopTimelapse,
+    ExtractSlitScan(String, PathBuf, SlitScanOptions),
     ViewImage(usize),
     SearchEntry(String),
     Loading(bool),
@@ -50,12 +146,16 @@ pub enum ImageListInput {
 #[derive(Debug)]
 pub enum ImageListOutput {
     ImageCount(usize),
+    VideoDamaged(String, DecodeReport),
     Notify(String, u32),
 }
 
 #[derive(Debug)]
 pub enum ImageListCommandOutput {
-    VideoExtractionCompleted(Result<models::Image>),
+    VideoExtractionCompleted(String, Result<(models::Image, DecodeReport)>),
+    CaptureCompleted(Result<CaptureResult>),
+    ClipExported(Result<ClipResult>),
+    TimelapseCompleted(Result<TimelapseResult>),
 }
 
 #[relm4::component(pub async)]
@@ -92,6 +192,24 @@ impl AsyncComponent for ImageListModel {
                         add_css_class: "flat",
                         connect_clicked => ImageListInput::ClearImageList,
                     },
+
+                    gtk::Button {
+                        #[watch]
+                        set_visible: model.capture_stop.is_some(),
+                        set_icon_name: icon_names::STOP_LARGE,
+                        set_tooltip: fl!("stop-capture"),
+                        add_css_class: "destructive-action",
+                        connect_clicked => ImageListInput::StopCapture,
+                    },
+
+                    gtk::Button {
+                        #[watch]
+                        set_visible: model.timelapse_stop.is_some(),
+                        set_icon_name: icon_names::STOP_LARGE,
+                        set_tooltip: fl!("stop-timelapse"),
+                        add_css_class: "destructive-action",
+                        connect_clicked => ImageListInput::StopTimelapse,
+                    },
                 },
 
                 gtk::Box {
@@ -211,15 +329,20 @@ impl AsyncComponent for ImageListModel {
                 layout_type,
                 show_timestamp,
                 dst_path,
+                options,
+                tracks,
+                preview,
             ) => {
                 widgets.spinner.start();
-                self.total_videos = video_list.len();
                 self.processed_videos = 0;
                 self.on_extract_videos_to_image(
                     video_list,
                     layout_type,
                     show_timestamp,
                     dst_path,
+                    options,
+                    tracks,
+                    preview,
                     &sender,
                 )
                 .await;
@@ -231,9 +354,11 @@ impl AsyncComponent for ImageListModel {
                 frame_rate,
                 show_timestamp,
                 dst_path,
+                options,
+                tracks,
+                preview,
             ) => {
                 widgets.spinner.start();
-                self.total_videos = 1;
                 self.processed_videos = 0;
                 self.on_extract_frames_from_video(
                     &video_path,
@@ -242,10 +367,86 @@ impl AsyncComponent for ImageListModel {
                     frame_rate,
                     show_timestamp,
                     dst_path,
+                    options,
+                    tracks,
+                    preview,
                     &sender,
                 )
                 .await;
             }
+            ImageListInput::StartCapture(video_path, dst_path, options) => {
+                self.on_start_capture(video_path, dst_path, options, &sender);
+            }
+            ImageListInput::CaptureProgress(event) => {
+                self.on_capture_progress(event, &sender);
+            }
+            ImageListInput::StopCapture => {
+                if let Some(stop) = &self.capture_stop {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            }
+            ImageListInput::ExportClip(video_path, time_start, time_end, dst_path, options) => {
+                let save_path = dst_path.to_str().unwrap_or("").to_string();
+                sender.oneshot_command(async move {
+                    let result = service::export_clip(
+                        video_path,
+                        save_path,
+                        time_start.into(),
+                        time_end.into(),
+                        options,
+                    )
+                    .await;
+                    ImageListCommandOutput::ClipExported(result)
+                });
+            }
+            ImageListInput::StackFrames(
+                video_path,
+                time_start,
+                time_end,
+                frame_rate,
+                dst_path,
+                options,
+            ) => {
+                widgets.spinner.start();
+                self.on_stack_frames(
+                    video_path, time_start, time_end, frame_rate, dst_path, options, &sender,
+                );
+            }
+            ImageListInput::ExtractSprites(
+                video_list,
+                show_timestamp,
+                dst_path,
+                options,
+                sprite,
+            ) => {
+                widgets.spinner.start();
+                self.processed_videos = 0;
+                self.on_extract_sprites(
+                    video_list,
+                    show_timestamp,
+                    dst_path,
+                    options,
+                    sprite,
+                    &sender,
+                );
+            }
+            ImageListInput::ExtractHeatmaps(video_list, interval, dst_path, options) => {
+                widgets.spinner.start();
+                self.processed_videos = 0;
+                self.on_extract_heatmaps(video_list, interval, dst_path, options, &sender);
+            }
+            ImageListInput::StartTimelapse(video_path, dst_path, options) => {
+                self.on_start_timelapse(video_path, dst_path, options, &sender);
+            }
+            ImageListInput::StopTimelapse => {
+                if let Some(stop) = &self.timelapse_stop {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            }
+            ImageListInput::ExtractSlitScan(video_path, dst_path, options) => {
+                widgets.spinner.start();
+                self.on_extract_slit_scan(video_path, dst_path, options, &sender);
+            }
             ImageListInput::SearchEntry(query) => {
                 self.apply_image_filter(&query).await;
             }
@@ -271,22 +472,35 @@ impl AsyncComponent for ImageListModel {
         _root: &Self::Root,
     ) {
         match message {
-            ImageListCommandOutput::VideoExtractionCompleted(result) => {
+            ImageListCommandOutput::VideoExtractionCompleted(video_path, result) => {
                 match result {
-                    Ok(img) => {
-                        let mut guard = self.image_list_factory.guard();
-                        guard.push_back(img);
-                        let count = guard.len();
-                        sender
-                            .output(ImageListOutput::ImageCount(count))
-                            .unwrap_or_default();
+                    Ok((img, report)) => {
+                        if is_displayable(&img.path) {
+                            self.push_image(img, &sender);
+                        } else {
+                            let msg = format!("{} {}", fl!("preview-saved"), img.path);
+                            sender
+                                .output(ImageListOutput::Notify(msg, 5))
+                                .unwrap_or_default();
+                        }
+
+                        if report.is_damaged() {
+                            sender
+                                .output(ImageListOutput::VideoDamaged(video_path, report))
+                                .unwrap_or_default();
+                        }
                     }
                     Err(err) => {
                         let msg_error = format!("{}: {}", fl!("generic-error"), err);
                         tracing::error!(msg_error);
-                        sender
-                            .output(ImageListOutput::Notify(msg_error, 3))
-                            .unwrap_or_default();
+
+                        // only a damaged stream marks the video, other
+                        // failures say nothing about the file
+                        let output = match err.downcast_ref::<DecodeError>() {
+                            Some(err) => ImageListOutput::VideoDamaged(video_path, err.report),
+                            None => ImageListOutput::Notify(msg_error, 3),
+                        };
+                        sender.output(output).unwrap_or_default();
                     }
                 }
 
@@ -294,63 +508,404 @@ impl AsyncComponent for ImageListModel {
                 if self.processed_videos == self.total_videos {
                     sender.input(ImageListInput::Loading(false));
                 }
+
+                self.running_videos = self.running_videos.saturating_sub(1);
+                self.start_pending_videos(&sender);
+            }
+            ImageListCommandOutput::ClipExported(result) => {
+                let msg = match result {
+                    Ok(result) => format!("{} {}", fl!("clip-exported"), result.clip_path),
+                    Err(err) => {
+                        let msg_error = format!("{}: {}", fl!("generic-error"), err);
+                        tracing::error!(msg_error);
+                        msg_error
+                    }
+                };
+                sender
+                    .output(ImageListOutput::Notify(msg, 5))
+                    .unwrap_or_default();
+            }
+            ImageListCommandOutput::TimelapseCompleted(result) => {
+                self.timelapse_stop = None;
+                let msg = match result {
+                    Ok(result) => format!("{} {}", fl!("timelapse-saved"), result.timelapse_path),
+                    Err(err) => {
+                        let msg_error = format!("{}: {}", fl!("generic-error"), err);
+                        tracing::error!(msg_error);
+                        msg_error
+                    }
+                };
+                sender
+                    .output(ImageListOutput::Notify(msg, 5))
+                    .unwrap_or_default();
+            }
+            ImageListCommandOutput::CaptureCompleted(result) => {
+                self.capture_stop = None;
+                match result {
+                    Ok(result) if !result.image_paths.is_empty() => {
+                        let img = models::Image {
+                            name: result.file_name,
+                            path: result.image_paths.last().cloned().unwrap_or_default(),
+                            total_images: result.image_paths.len(),
+                            thumbnail_size: self.thumbnail_size,
+                        };
+                        self.push_image(img, &sender);
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        let msg_error = format!("{}: {}", fl!("generic-error"), err);
+                        tracing::error!(msg_error);
+                        sender
+                            .output(ImageListOutput::Notify(msg_error, 3))
+                            .unwrap_or_default();
+                    }
+                }
             }
         }
     }
 }
 
 impl ImageListModel {
+    #[allow(clippy::too_many_arguments)]
     async fn on_extract_videos_to_image(
         &mut self,
         video_list: Vec<String>,
         layout_type: models::LayoutType,
         show_timestamp: bool,
         dst_path: PathBuf,
+        options: DecodeOptions,
+        tracks: models::TrackSelection,
+        preview: Option<PreviewOptions>,
         sender: &AsyncComponentSender<ImageListModel>,
     ) {
         let cols = layout_type.value().1;
         let rows = layout_type.value().0;
         let layout_name = layout_type.to_string();
+        let output = preview.map_or(JobOutput::ContactSheet, JobOutput::Preview);
 
-        for video_path in video_list.iter() {
-            if let Some(filename) = Path::new(video_path).file_stem() {
-                let filename = format!("{}_{}", layout_name, filename.to_str().unwrap_or_default());
-                let mut image_path = dst_path.clone().join(filename);
-                image_path.set_extension("jpeg");
+        self.parallel_videos = parallel_videos();
 
-                let image_path = image_path.to_str().unwrap().to_string();
-                let video_path = video_path.to_owned();
-                let thumbnail_size = self.thumbnail_size;
+        let mut jobs = Vec::new();
+        for video_path in video_list.iter() {
+            let filename = media_name(video_path);
+            if !filename.is_empty() {
+                let filename = format!("{}_{}", layout_name, filename);
+                let mut job = |filename: String, options: DecodeOptions, tracks: Vec<usize>| {
+                    let mut image_path = dst_path.clone().join(filename);
+                    image_path.set_extension(preview.map_or("jpeg", |p| p.format.extension()));
 
-                sender.oneshot_command(async move {
-                    match service::dump_video_frames_into_image(
-                        video_path,
-                        image_path.clone(),
+                    jobs.push(VideoJob {
+                        video_path: video_path.to_owned(),
+                        image_path: image_path.to_str().unwrap().to_string(),
                         cols,
                         rows,
                         show_timestamp,
+                        options,
+                        tracks,
+                        output,
+                    });
+                };
+
+                if tracks.tracks.is_empty() {
+                    job(filename, options, Vec::new());
+                } else if tracks.side_by_side && tracks.tracks.len() > 1 && preview.is_none() {
+                    job(filename, options, tracks.tracks.clone());
+                } else {
+                    // one contact sheet per track
+                    for &track in tracks.tracks.iter() {
+                        let options = DecodeOptions {
+                            video_track: Some(track),
+                            ..options
+                        };
+                        job(
+                            format!("{}_track{}", filename, track + 1),
+                            options,
+                            Vec::new(),
+                        );
+                    }
+                }
+            }
+        }
+
+        self.total_videos = jobs.len();
+        self.pending_videos.extend(jobs);
+        self.start_pending_videos(sender);
+    }
+
+    /// Queues one sprite job per video, each writing its sheets and WebVTT
+    /// track into a directory named after the video.
+    fn on_extract_sprites(
+        &mut self,
+        video_list: Vec<String>,
+        show_timestamp: bool,
+        dst_path: PathBuf,
+        options: DecodeOptions,
+        sprite: SpriteOptions,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        self.parallel_videos = parallel_videos();
+
+        let save_path = dst_path.to_str().unwrap_or("").to_string();
+        let jobs: Vec<VideoJob> = video_list
+            .into_iter()
+            .map(|video_path| VideoJob {
+                video_path,
+                image_path: save_path.clone(),
+                cols: sprite.columns,
+                rows: sprite.rows,
+                show_timestamp,
+                options,
+                tracks: Vec::new(),
+                output: JobOutput::Sprites(sprite),
+            })
+            .collect();
+
+        self.total_videos = jobs.len();
+        self.pending_videos.extend(jobs);
+        self.start_pending_videos(sender);
+    }
+
+    /// Queues one heatmap job per video, saved next to the contact sheets.
+    fn on_extract_heatmaps(
+        &mut self,
+        video_list: Vec<String>,
+        interval: u32,
+        dst_path: PathBuf,
+        options: DecodeOptions,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        self.parallel_videos = parallel_videos();
+
+        let jobs: Vec<VideoJob> = video_list
+            .into_iter()
+            .filter_map(|video_path| {
+                let filename = media_name(&video_path);
+                if filename.is_empty() {
+                    return None;
+                }
+                let filename = match options.video_track {
+                    Some(track) => format!("heatmap_{}_track{}", filename, track + 1),
+                    None => format!("heatmap_{}", filename),
+                };
+                let mut image_path = dst_path.join(filename);
+                image_path.set_extension("jpeg");
+
+                Some(VideoJob {
+                    video_path,
+                    image_path: image_path.to_str().unwrap_or("").to_string(),
+                    cols: 1,
+                    rows: 1,
+                    show_timestamp: false,
+                    options,
+                    tracks: Vec::new(),
+                    output: JobOutput::Heatmap(interval),
+                })
+            })
+            .collect();
+
+        self.total_videos = jobs.len();
+        self.pending_videos.extend(jobs);
+        self.start_pending_videos(sender);
+    }
+
+    /// Queues a single video, joining the batch that is still running so
+    /// the spinner only stops once all of it is done.
+    fn queue_video(&mut self, job: VideoJob, sender: &AsyncComponentSender<ImageListModel>) {
+        self.parallel_videos = parallel_videos();
+
+        if self.running_videos == 0 && self.pending_videos.is_empty() {
+            self.processed_videos = 0;
+            self.total_videos = 0;
+        }
+        self.total_videos += 1;
+        self.pending_videos.push_back(job);
+        self.start_pending_videos(sender);
+    }
+
+    /// Starts queued videos until the parallel limit is reached, so selecting
+    /// hundreds of videos does not start hundreds of decoders at once.
+    fn start_pending_videos(&mut self, sender: &AsyncComponentSender<ImageListModel>) {
+        while self.running_videos < self.parallel_videos {
+            let Some(job) = self.pending_videos.pop_front() else {
+                break;
+            };
+            self.running_videos += 1;
+
+            let thumbnail_size = self.thumbnail_size;
+
+            sender.oneshot_command(async move {
+                let VideoJob {
+                    video_path,
+                    image_path,
+                    cols,
+                    rows,
+                    show_timestamp,
+                    options,
+                    tracks,
+                    output,
+                } = job;
+
+                if let JobOutput::Sprites(sprite) = output {
+                    return match service::dump_video_sprites(
+                        video_path.clone(),
+                        image_path,
+                        show_timestamp,
+                        sprite,
+                        options,
                     )
                     .await
                     {
-                        Ok(_) => {
-                            let filename =
-                                if let Some(filename) = Path::new(&image_path).file_name() {
-                                    filename.to_str().unwrap().to_string()
-                                } else {
-                                    String::new()
-                                };
+                        Ok(result) => {
                             let img = models::Image {
-                                name: filename,
-                                path: image_path,
-                                total_images: 1,
+                                name: result.file_name,
+                                path: result.sheet_paths.last().cloned().unwrap_or_default(),
+                                total_images: result.sheet_paths.len(),
                                 thumbnail_size,
                             };
-                            ImageListCommandOutput::VideoExtractionCompleted(Ok(img))
+                            ImageListCommandOutput::VideoExtractionCompleted(
+                                video_path,
+                                Ok((img, result.report)),
+                            )
                         }
-                        Err(err) => ImageListCommandOutput::VideoExtractionCompleted(Err(err)),
+                        Err(err) => {
+                            ImageListCommandOutput::VideoExtractionCompleted(video_path, Err(err))
+                        }
+                    };
+                }
+
+                if let JobOutput::Stack {
+                    time_start,
+                    time_end,
+                    frame_rate,
+                    options,
+                } = output
+                {
+                    let result = service::stack_frames(
+                        video_path.clone(),
+                        image_path,
+                        time_start.into(),
+                        time_end.into(),
+                        frame_rate,
+                        options,
+                    )
+                    .await
+                    .map(|result| {
+                        let img = models::Image {
+                            name: result.file_name,
+                            path: result.image_path,
+                            total_images: 1,
+                            thumbnail_size,
+                        };
+                        (img, result.report)
+                    });
+                    return ImageListCommandOutput::VideoExtractionCompleted(video_path, result);
+                }
+
+                if let JobOutput::SlitScan(slit_scan) = output {
+                    let result = service::dump_slit_scan(video_path.clone(), image_path, slit_scan)
+                        .await
+                        .map(|result| {
+                            let img = models::Image {
+                                name: result.file_name,
+                                path: result.image_paths.last().cloned().unwrap_or_default(),
+                                total_images: result.image_paths.len(),
+                                thumbnail_size,
+                            };
+                            (img, result.report)
+                        });
+                    return ImageListCommandOutput::VideoExtractionCompleted(video_path, result);
+                }
+
+                if let JobOutput::Frames {
+                    time_start,
+                    time_end,
+                    frame_rate,
+                    preview,
+                } = output
+                {
+                    return extract_frames(
+                        video_path,
+                        image_path,
+                        time_start,
+                        time_end,
+                        frame_rate,
+                        show_timestamp,
+                        preview,
+                        options,
+                        thumbnail_size,
+                    )
+                    .await;
+                }
+
+                let result = match output {
+                    JobOutput::Preview(preview) => {
+                        service::dump_video_preview(
+                            video_path.clone(),
+                            image_path.clone(),
+                            cols * rows,
+                            show_timestamp,
+                            preview,
+                            options,
+                        )
+                        .await
                     }
-                });
-            }
+                    JobOutput::Heatmap(interval) => {
+                        service::dump_motion_heatmap(
+                            video_path.clone(),
+                            image_path.clone(),
+                            interval,
+                            options,
+                        )
+                        .await
+                    }
+                    _ if tracks.len() > 1 => {
+                        service::dump_tracks_into_image(
+                            video_path.clone(),
+                            image_path.clone(),
+                            cols,
+                            rows,
+                            tracks,
+                            show_timestamp,
+                            options,
+                        )
+                        .await
+                    }
+                    _ => {
+                        service::dump_video_frames_into_image(
+                            video_path.clone(),
+                            image_path.clone(),
+                            cols,
+                            rows,
+                            show_timestamp,
+                            options,
+                        )
+                        .await
+                    }
+                };
+
+                match result {
+                    Ok(report) => {
+                        let filename = if let Some(filename) = Path::new(&image_path).file_name() {
+                            filename.to_str().unwrap().to_string()
+                        } else {
+                            String::new()
+                        };
+                        let img = models::Image {
+                            name: filename,
+                            path: image_path,
+                            total_images: 1,
+                            thumbnail_size,
+                        };
+                        ImageListCommandOutput::VideoExtractionCompleted(
+                            video_path,
+                            Ok((img, report)),
+                        )
+                    }
+                    Err(err) => {
+                        ImageListCommandOutput::VideoExtractionCompleted(video_path, Err(err))
+                    }
+                }
+            });
         }
     }
 
@@ -363,42 +918,200 @@ impl ImageListModel {
         frame_rate: u32,
         show_timestamp: bool,
         dst_path: PathBuf,
+        options: DecodeOptions,
+        tracks: models::TrackSelection,
+        preview: Option<PreviewOptions>,
         sender: &AsyncComponentSender<ImageListModel>,
     ) {
-        let video_path = video_path.to_owned();
+        self.parallel_videos = parallel_videos();
+
+        // every track is extracted into its own directory
+        let video_tracks: Vec<Option<usize>> = if tracks.tracks.is_empty() {
+            vec![None]
+        } else {
+            tracks.tracks.iter().map(|&track| Some(track)).collect()
+        };
         let save_path = dst_path.to_str().unwrap_or("").to_string();
-        let thumbnail_size = self.thumbnail_size;
+        let jobs: Vec<VideoJob> = video_tracks
+            .into_iter()
+            .map(|video_track| VideoJob {
+                video_path: video_path.to_owned(),
+                image_path: save_path.clone(),
+                cols: 0,
+                rows: 0,
+                show_timestamp,
+                options: DecodeOptions {
+                    video_track,
+                    ..options
+                },
+                tracks: Vec::new(),
+                output: JobOutput::Frames {
+                    time_start,
+                    time_end,
+                    frame_rate,
+                    preview,
+                },
+            })
+            .collect();
 
-        sender.oneshot_command(async move {
-            match service::dump_video_frames_by_time(
-                video_path,
-                save_path,
-                time_start.into(),
-                time_end.into(),
+        self.total_videos = jobs.len();
+        self.pending_videos.extend(jobs);
+        self.start_pending_videos(sender);
+    }
+
+    /// Combines the frames of a range into a single still.
+    #[allow(clippy::too_many_arguments)]
+    fn on_stack_frames(
+        &mut self,
+        video_path: String,
+        time_start: u32,
+        time_end: u32,
+        frame_rate: u32,
+        dst_path: PathBuf,
+        options: StackOptions,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        let job = VideoJob {
+            video_path,
+            image_path: dst_path.to_str().unwrap_or("").to_string(),
+            cols: 1,
+            rows: 1,
+            show_timestamp: false,
+            options: options.decode,
+            tracks: Vec::new(),
+            output: JobOutput::Stack {
+                time_start,
+                time_end,
                 frame_rate,
-                show_timestamp,
-            )
-            .await
-            {
-                Ok(result) => {
-                    let image_path = result
-                        .image_paths
-                        .last()
-                        .map(|s| s.to_owned())
-                        .unwrap_or_default();
-                    let img = models::Image {
-                        name: result.file_name,
-                        path: image_path,
-                        total_images: result.image_paths.len(),
-                        thumbnail_size,
-                    };
-                    ImageListCommandOutput::VideoExtractionCompleted(Ok(img))
-                }
-                Err(err) => ImageListCommandOutput::VideoExtractionCompleted(Err(err)),
-            }
+                options,
+            },
+        };
+        self.queue_video(job, sender);
+    }
+
+    /// Starts a long-lived capture that saves a snapshot every few seconds
+    /// until it is stopped from the header button.
+    fn on_start_capture(
+        &mut self,
+        video_path: String,
+        dst_path: PathBuf,
+        options: CaptureOptions,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        if self.capture_stop.is_some() {
+            sender
+                .output(ImageListOutput::Notify(
+                    fl!("capture-running").to_string(),
+                    3,
+                ))
+                .unwrap_or_default();
+            return;
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        self.capture_stop = Some(stop.clone());
+        let save_path = dst_path.to_str().unwrap_or("").to_string();
+        let input_sender = sender.input_sender().clone();
+
+        sender.oneshot_command(async move {
+            let result =
+                service::capture_snapshots(video_path, save_path, options, stop, move |event| {
+                    input_sender.emit(ImageListInput::CaptureProgress(event))
+                })
+                .await;
+            ImageListCommandOutput::CaptureCompleted(result)
+        });
+    }
+
+    /// Starts a time-lapse export that runs until the end of the file or
+    /// until it is stopped from the header button.
+    fn on_start_timelapse(
+        &mut self,
+        video_path: String,
+        dst_path: PathBuf,
+        options: TimelapseOptions,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        if self.timelapse_stop.is_some() {
+            sender
+                .output(ImageListOutput::Notify(
+                    fl!("timelapse-running").to_string(),
+                    3,
+                ))
+                .unwrap_or_default();
+            return;
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        self.timelapse_stop = Some(stop.clone());
+        let save_path = dst_path.to_str().unwrap_or("").to_string();
+
+        sender.oneshot_command(async move {
+            let result = service::export_timelapse(video_path, save_path, options, stop).await;
+            ImageListCommandOutput::TimelapseCompleted(result)
         });
     }
 
+    /// Saves the slit-scan strips of a video, the last one is shown.
+    fn on_extract_slit_scan(
+        &mut self,
+        video_path: String,
+        dst_path: PathBuf,
+        options: SlitScanOptions,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        let job = VideoJob {
+            video_path,
+            image_path: dst_path.to_str().unwrap_or("").to_string(),
+            cols: 1,
+            rows: 1,
+            show_timestamp: false,
+            options: options.decode,
+            tracks: Vec::new(),
+            output: JobOutput::SlitScan(options),
+        };
+        self.queue_video(job, sender);
+    }
+
+    fn on_capture_progress(
+        &mut self,
+        event: CaptureEvent,
+        sender: &AsyncComponentSender<ImageListModel>,
+    ) {
+        match event {
+            CaptureEvent::Snapshot(_) => {}
+            CaptureEvent::ContactSheet(sheet_path) => {
+                let name = Path::new(&sheet_path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let img = models::Image {
+                    name,
+                    path: sheet_path,
+                    total_images: 1,
+                    thumbnail_size: self.thumbnail_size,
+                };
+                self.push_image(img, sender);
+            }
+            CaptureEvent::Interrupted(err) => {
+                let msg = format!("{}: {}", fl!("capture-interrupted"), err);
+                tracing::warn!(msg);
+                sender
+                    .output(ImageListOutput::Notify(msg, 3))
+                    .unwrap_or_default();
+            }
+        }
+    }
+
+    fn push_image(&mut self, img: models::Image, sender: &AsyncComponentSender<ImageListModel>) {
+        let mut guard = self.image_list_factory.guard();
+        guard.push_back(img);
+        let count = guard.len();
+        sender
+            .output(ImageListOutput::ImageCount(count))
+            .unwrap_or_default();
+    }
+
     async fn apply_image_filter(&mut self, query: &str) {
         let image_widget = self.image_list_factory.widget();
 
@@ -463,3 +1176,80 @@ impl ImageListModel {
         }
     }
 }
+
+/// Whether the grid can show an output. `gtk::Picture` does not play WebP
+/// and MP4 previews, whose path is reported instead.
+fn is_displayable(path: &str) -> bool {
+    let extension = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+    ![PreviewFormat::Webp, PreviewFormat::Mp4]
+        .iter()
+        .any(|format| extension.as_deref() == Some(format.extension()))
+}
+
+fn parallel_videos() -> usize {
+    match settings::get_settings() {
+        Ok(settings_toml) if settings_toml.parallel_videos > 0 => settings_toml.parallel_videos,
+        _ => models::preference::automatic_parallel_videos(),
+    }
+}
+
+/// Saves the frames of a time range, or their animation, for a queued job.
+#[allow(clippy::too_many_arguments)]
+async fn extract_frames(
+    video_path: String,
+    save_path: String,
+    time_start: u32,
+    time_end: u32,
+    frame_rate: u32,
+    show_timestamp: bool,
+    preview: Option<PreviewOptions>,
+    options: DecodeOptions,
+    thumbnail_size: i32,
+) -> ImageListCommandOutput {
+    if let Some(preview) = preview {
+        let result = service::dump_video_preview_by_time(
+            video_path.clone(),
+            save_path,
+            time_start.into(),
+            time_end.into(),
+            frame_rate,
+            show_timestamp,
+            preview,
+            options,
+        )
+        .await
+        .map(|result| {
+            let img = models::Image {
+                name: result.file_name,
+                path: result.preview_path,
+                total_images: 1,
+                thumbnail_size,
+            };
+            (img, result.report)
+        });
+        return ImageListCommandOutput::VideoExtractionCompleted(video_path, result);
+    }
+
+    let result = service::dump_video_frames_by_time(
+        video_path.clone(),
+        save_path,
+        time_start.into(),
+        time_end.into(),
+        frame_rate,
+        show_timestamp,
+        options,
+    )
+    .await
+    .map(|result| {
+        let img = models::Image {
+            name: result.file_name,
+            path: result.image_paths.last().cloned().unwrap_or_default(),
+            total_images: result.image_paths.len(),
+            thumbnail_size,
+        };
+        (img, result.report)
+    });
+    ImageListCommandOutput::VideoExtractionCompleted(video_path, result)
+}
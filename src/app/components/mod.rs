@@ -1,5 +1,6 @@
 pub mod about_dialog;
 pub mod content;
+pub mod details_dialog;
 pub mod extract_dialog;
 pub mod image_list;
 pub mod preferences;
@@ -13,6 +13,7 @@ use relm4::{
 
 use crate::app::{config::settings, models};
 use crate::fl;
+use core_vimpeg::video::{Deinterlace, InputFormat};
 
 #[derive(Debug)]
 pub struct PreferencesModel {
@@ -23,6 +24,18 @@ pub struct PreferencesModel {
 pub enum PreferencesInput {
     SetColorScheme(models::ColorScheme),
     SetLanguage(models::Language),
+    SetThumbnailPosition(u32),
+    SetDeinterlace(Deinterlace),
+    SetDecoderThreads(usize),
+    SetParallelVideos(usize),
+    SetInputFormat(InputFormat),
+    SetInputFrameRate(f64),
+    SetGenerateTimestamps(bool),
+    SetNetworkTimeout(u32),
+    SetReconnect(bool),
+    SetRtspTcp(bool),
+    SetPreviewSize(u32),
+    SetPreviewFrameRate(u32),
 }
 
 #[relm4::component(pub async)]
@@ -145,6 +158,264 @@ impl AsyncComponent for PreferencesModel {
                                         }
                                     },
                                 },
+                            },
+
+                            add = &adw::PreferencesGroup {
+                                set_title: fl!("thumbnail"),
+                                adw::ActionRow {
+                                    set_title: fl!("thumbnail-position"),
+                                    set_subtitle: fl!("thumbnail-position-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.thumbnail_position as f64, 0f64, 95f64, 1f64, 5f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetThumbnailPosition(spin_button.value() as u32))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
+                                adw::ComboRow {
+                                    set_title: fl!("deinterlace"),
+                                    set_subtitle: fl!("deinterlace-description"),
+                                    set_model: Some(&gtk::StringList::new(&[
+                                        fl!("deinterlace-off"),
+                                        fl!("deinterlace-fields"),
+                                        fl!("deinterlace-bob"),
+                                        fl!("deinterlace-quality"),
+                                    ])),
+                                    set_selected: match model.preference.deinterlace {
+                                        Deinterlace::Off => 0,
+                                        Deinterlace::Fields => 1,
+                                        Deinterlace::Bob => 2,
+                                        Deinterlace::Quality => 3,
+                                    },
+                                    connect_selected_notify[sender] => move |combo_row| {
+                                        let deinterlace = match combo_row.selected() {
+                                            1 => Deinterlace::Fields,
+                                            2 => Deinterlace::Bob,
+                                            3 => Deinterlace::Quality,
+                                            _ => Deinterlace::Off,
+                                        };
+                                        sender
+                                            .input_sender()
+                                            .send(PreferencesInput::SetDeinterlace(deinterlace))
+                                            .unwrap_or_default();
+                                    },
+                                },
+                            },
+
+                            add = &adw::PreferencesGroup {
+                                set_title: fl!("performance"),
+                                adw::ActionRow {
+                                    set_title: fl!("decoder-threads"),
+                                    set_subtitle: fl!("decoder-threads-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.decoder_threads as f64, 0f64, 64f64, 1f64, 4f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetDecoderThreads(spin_button.value() as usize))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
+                                adw::ActionRow {
+                                    set_title: fl!("parallel-videos"),
+                                    set_subtitle: fl!("parallel-videos-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.parallel_videos as f64, 0f64, 64f64, 1f64, 4f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetParallelVideos(spin_button.value() as usize))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
+                            },
+
+                            add = &adw::PreferencesGroup {
+                                set_title: fl!("input"),
+                                set_description: Some(fl!("input-description")),
+                                adw::ComboRow {
+                                    set_title: fl!("input-format"),
+                                    set_model: Some(&gtk::StringList::new(&[
+                                        fl!("input-format-auto"),
+                                        "H.264",
+                                        "H.265 / HEVC",
+                                        "MPEG-4",
+                                        "Motion JPEG",
+                                        "MPEG-TS",
+                                        "MPEG-PS",
+                                        "Dahua DAV",
+                                    ])),
+                                    set_selected: match model.preference.input.format {
+                                        InputFormat::Auto => 0,
+                                        InputFormat::H264 => 1,
+                                        InputFormat::Hevc => 2,
+                                        InputFormat::Mpeg4 => 3,
+                                        InputFormat::Mjpeg => 4,
+                                        InputFormat::MpegTs => 5,
+                                        InputFormat::MpegPs => 6,
+                                        InputFormat::Dhav => 7,
+                                    },
+                                    connect_selected_notify[sender] => move |combo_row| {
+                                        let format = match combo_row.selected() {
+                                            1 => InputFormat::H264,
+                                            2 => InputFormat::Hevc,
+                                            3 => InputFormat::Mpeg4,
+                                            4 => InputFormat::Mjpeg,
+                                            5 => InputFormat::MpegTs,
+                                            6 => InputFormat::MpegPs,
+                                            7 => InputFormat::Dhav,
+                                            _ => InputFormat::Auto,
+                                        };
+                                        sender
+                                            .input_sender()
+                                            .send(PreferencesInput::SetInputFormat(format))
+                                            .unwrap_or_default();
+                                    },
+                                },
+                                adw::ActionRow {
+                                    set_title: fl!("input-frame-rate"),
+                                    set_subtitle: fl!("input-frame-rate-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_digits: 2,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.input.frame_rate.unwrap_or_default(), 0f64, 240f64, 1f64, 5f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetInputFrameRate(spin_button.value()))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
+                                adw::ActionRow {
+                                    set_title: fl!("generate-timestamps"),
+                                    set_subtitle: fl!("generate-timestamps-description"),
+                                    add_suffix = &gtk::Switch {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_active: model.preference.input.generate_timestamps,
+                                        connect_state_set[sender] => move |_, state| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetGenerateTimestamps(state))
+                                                .unwrap_or_default();
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                    },
+                                },
+                            },
+
+                            add = &adw::PreferencesGroup {
+                                set_title: fl!("network"),
+                                set_description: Some(fl!("network-description")),
+                                adw::ActionRow {
+                                    set_title: fl!("network-timeout"),
+                                    set_subtitle: fl!("network-timeout-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.input.timeout as f64, 0f64, 300f64, 1f64, 10f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetNetworkTimeout(spin_button.value() as u32))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
+                                adw::ActionRow {
+                                    set_title: fl!("reconnect"),
+                                    set_subtitle: fl!("reconnect-description"),
+                                    add_suffix = &gtk::Switch {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_active: model.preference.input.reconnect,
+                                        connect_state_set[sender] => move |_, state| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetReconnect(state))
+                                                .unwrap_or_default();
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                    },
+                                },
+                                adw::ActionRow {
+                                    set_title: fl!("rtsp-tcp"),
+                                    set_subtitle: fl!("rtsp-tcp-description"),
+                                    add_suffix = &gtk::Switch {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_active: model.preference.input.rtsp_tcp,
+                                        connect_state_set[sender] => move |_, state| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetRtspTcp(state))
+                                                .unwrap_or_default();
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                    },
+                                },
+                            },
+
+                            add = &adw::PreferencesGroup {
+                                set_title: fl!("animated-preview"),
+                                set_description: Some(fl!("animated-preview-description")),
+                                adw::ActionRow {
+                                    set_title: fl!("preview-size"),
+                                    set_subtitle: fl!("preview-size-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.preview.size as f64, 64f64, 1920f64, 16f64, 64f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetPreviewSize(spin_button.value() as u32))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
+                                adw::ActionRow {
+                                    set_title: fl!("preview-frame-rate"),
+                                    set_subtitle: fl!("preview-frame-rate-description"),
+                                    add_suffix = &gtk::SpinButton {
+                                        set_halign: gtk::Align::Center,
+                                        set_valign: gtk::Align::Center,
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            model.preference.preview.frame_rate as f64, 1f64, 30f64, 1f64, 5f64, 0f64,
+                                        ),
+                                        connect_value_changed[sender] => move |spin_button| {
+                                            sender
+                                                .input_sender()
+                                                .send(PreferencesInput::SetPreviewFrameRate(spin_button.value() as u32))
+                                                .unwrap_or_default();
+                                        },
+                                    },
+                                },
                             }
                         }
                     }
@@ -163,7 +434,16 @@ impl AsyncComponent for PreferencesModel {
         if let Ok(settings_toml) = settings::get_settings() {
             let color_scheme = settings_toml.theme;
             let language = models::Language::from_str(settings_toml.language.as_str()).unwrap();
-            preference = models::Preference::new(color_scheme, language);
+            preference = models::Preference::new(
+                color_scheme,
+                language,
+                settings_toml.thumbnail_position,
+                settings_toml.deinterlace,
+                settings_toml.decoder_threads,
+                settings_toml.parallel_videos,
+                settings_toml.input,
+                settings_toml.preview,
+            );
         }
 
         let model = PreferencesModel { preference };
@@ -189,6 +469,42 @@ impl AsyncComponent for PreferencesModel {
                 self.preference.language = language;
                 self.show_dialog(root);
             }
+            PreferencesInput::SetThumbnailPosition(thumbnail_position) => {
+                self.preference.thumbnail_position = thumbnail_position;
+            }
+            PreferencesInput::SetDeinterlace(deinterlace) => {
+                self.preference.deinterlace = deinterlace;
+            }
+            PreferencesInput::SetDecoderThreads(decoder_threads) => {
+                self.preference.decoder_threads = decoder_threads;
+            }
+            PreferencesInput::SetParallelVideos(parallel_videos) => {
+                self.preference.parallel_videos = parallel_videos;
+            }
+            PreferencesInput::SetInputFormat(format) => {
+                self.preference.input.format = format;
+            }
+            PreferencesInput::SetInputFrameRate(frame_rate) => {
+                self.preference.input.frame_rate = (frame_rate > 0.0).then_some(frame_rate);
+            }
+            PreferencesInput::SetGenerateTimestamps(generate_timestamps) => {
+                self.preference.input.generate_timestamps = generate_timestamps;
+            }
+            PreferencesInput::SetNetworkTimeout(timeout) => {
+                self.preference.input.timeout = timeout;
+            }
+            PreferencesInput::SetReconnect(reconnect) => {
+                self.preference.input.reconnect = reconnect;
+            }
+            PreferencesInput::SetRtspTcp(rtsp_tcp) => {
+                self.preference.input.rtsp_tcp = rtsp_tcp;
+            }
+            PreferencesInput::SetPreviewSize(size) => {
+                self.preference.preview.size = size;
+            }
+            PreferencesInput::SetPreviewFrameRate(frame_rate) => {
+                self.preference.preview.frame_rate = frame_rate;
+            }
         }
 
         if let Err(error) = settings::save_preferences(&self.preference).await {
@@ -12,6 +12,7 @@ use relm4_components::open_dialog::{
 use relm4_icons::icon_names;
 
 use crate::fl;
+use core_vimpeg::video::is_url;
 
 pub struct SearchBarModel {
     open_dialog: Controller<OpenDialog>,
@@ -25,6 +26,7 @@ pub enum SearchBarInput {
     SearchCompleted,
     OpenFileRequest,
     OpenFileResponse(PathBuf),
+    PathChanged(String),
     Ignore,
 }
 
@@ -51,10 +53,11 @@ impl AsyncComponent for SearchBarModel {
 
             append = &gtk::Entry {
                 #[watch]
+                #[block_signal(path_changed)]
                 set_text: &model.file_path.to_str().unwrap_or_default(),
                 set_hexpand: true,
                 set_halign: gtk::Align::Fill,
-                set_placeholder_text: Some(fl!("directory")),
+                set_placeholder_text: Some(fl!("directory-or-url")),
                 set_secondary_icon_name: Some(icon_names::FOLDER_OPEN_FILLED),
                 set_secondary_icon_tooltip_text: Some(fl!("select-directory")),
                 connect_icon_release[sender] => move |_, icon_position| {
@@ -62,6 +65,9 @@ impl AsyncComponent for SearchBarModel {
                         sender.input(SearchBarInput::OpenFileRequest);
                     }
                 },
+                connect_changed[sender] => move |entry| {
+                    sender.input(SearchBarInput::PathChanged(entry.text().to_string()));
+                } @path_changed,
             },
 
             append = &gtk::Button {
@@ -116,7 +122,9 @@ impl AsyncComponent for SearchBarModel {
     ) {
         match message {
             SearchBarInput::StartSearch => {
-                if self.file_path.exists() {
+                // URLs of HTTP files, HLS playlists and RTSP cameras are
+                // opened as a single video
+                if self.file_path.exists() || is_url(&self.file_path) {
                     self.stopped = false;
                     let file_path = self.file_path.clone();
                     sender
@@ -138,6 +146,9 @@ impl AsyncComponent for SearchBarModel {
             SearchBarInput::OpenFileResponse(path) => {
                 self.file_path = path;
             }
+            SearchBarInput::PathChanged(path) => {
+                self.file_path = PathBuf::from(path);
+            }
             SearchBarInput::Ignore => {}
         }
     }
@@ -16,20 +16,31 @@ use relm4_icons::icon_names;
 
 use super::toolbar::{ToolBarInput, ToolBarModel, ToolBarOutput};
 use crate::app::{
-    components::extract_dialog::{
-        ExtractDialogInput, ExtractDialogModel, ExtractDialogOutput, ExtractDialogResponse,
-        ExtractDialogType,
+    components::{
+        details_dialog::{DetailsDialogInput, DetailsDialogModel},
+        extract_dialog::{
+            ExtractDialogInput, ExtractDialogModel, ExtractDialogOutput, ExtractDialogResponse,
+            ExtractDialogType,
+        },
     },
+    config::settings,
     factories::video::{VideoInput, VideoModel, VideoOutput},
     models,
 };
 use crate::fl;
-use core_vimpeg::video::service;
+use core_vimpeg::video::{
+    service::{
+        self, CaptureOptions, ClipOptions, DecodeOptions, PreviewOptions, SlitScanOptions,
+        SpriteOptions, StackOptions, TimelapseOptions,
+    },
+    Deinterlace, InputOptions,
+};
 
 pub struct VideoListModel {
     toolbar: AsyncController<ToolBarModel>,
     video_list_factory: AsyncFactoryVecDeque<VideoModel>,
     extract_dialog: Controller<ExtractDialogModel>,
+    details_dialog: Controller<DetailsDialogModel>,
     video_list_filter: models::VideoFilter,
     thumbnail_size: i32,
 }
@@ -39,11 +50,13 @@ impl VideoListModel {
         toolbar: AsyncController<ToolBarModel>,
         video_list_factory: AsyncFactoryVecDeque<VideoModel>,
         extract_dialog: Controller<ExtractDialogModel>,
+        details_dialog: Controller<DetailsDialogModel>,
     ) -> Self {
         Self {
             toolbar,
             video_list_factory,
             extract_dialog,
+            details_dialog,
             video_list_filter: models::VideoFilter::default(),
             thumbnail_size: models::video::THUMBNAIL_SIZE,
         }
@@ -55,6 +68,8 @@ pub enum VideoListInput {
     StartSearch(PathBuf),
     SearchCompleted(usize),
     PlayVideo(usize),
+    ShowDetails(usize),
+    MarkDamaged(String, service::DecodeReport),
     ZoomIn,
     ZoomOut,
     OpenExtractDialog,
@@ -73,8 +88,33 @@ pub enum VideoListInput {
 pub enum VideoListOutput {
     SearchCompleted(usize),
     FilterResult(usize),
-    ExtractVideosToImage(Vec<String>, models::LayoutType, bool, PathBuf),
-    ExtractFramesFromVideo(String, u32, u32, u32, bool, PathBuf),
+    ExtractVideosToImage(
+        Vec<String>,
+        models::LayoutType,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    ExtractFramesFromVideo(
+        String,
+        u32,
+        u32,
+        u32,
+        bool,
+        PathBuf,
+        DecodeOptions,
+        models::TrackSelection,
+        Option<PreviewOptions>,
+    ),
+    StartCapture(String, PathBuf, CaptureOptions),
+    ExportClip(String, u32, u32, PathBuf, ClipOptions),
+    StackFrames(String, u32, u32, u32, PathBuf, StackOptions),
+    ExtractSprites(Vec<String>, bool, PathBuf, DecodeOptions, SpriteOptions),
+    ExtractHeatmaps(Vec<String>, u32, PathBuf, DecodeOptions),
+    StartTimelapse(String, PathBuf, TimelapseOptions),
+    ExtractSlitScan(String, PathBuf, SlitScanOptions),
     Notify(String, u32),
 }
 
@@ -189,6 +229,7 @@ impl AsyncComponent for VideoListModel {
             sender.input_sender(),
             |output| match output {
                 VideoOutput::Selected(is_selected) => VideoListInput::SelectedVideo(is_selected),
+                VideoOutput::ShowDetails(index) => VideoListInput::ShowDetails(index),
             },
         );
 
@@ -201,10 +242,16 @@ impl AsyncComponent for VideoListModel {
                 }
             });
 
+        let details_dialog_controller = DetailsDialogModel::builder()
+            .transient_for(&root)
+            .launch(())
+            .detach();
+
         let model = VideoListModel::new(
             tool_bar_controller,
             video_list_factory,
             convert_dialog_controller,
+            details_dialog_controller,
         );
         let video_list_widget = model.video_list_factory.widget();
         let widgets = view_output!();
@@ -239,6 +286,24 @@ impl AsyncComponent for VideoListModel {
             VideoListInput::PlayVideo(index) => {
                 self.on_play_video(index, &sender).await;
             }
+            VideoListInput::ShowDetails(index) => {
+                if let Some(video_model) = self.video_list_factory.guard().get(index) {
+                    let video = video_model.video.clone();
+                    self.details_dialog.emit(DetailsDialogInput::Show(video));
+                }
+            }
+            VideoListInput::MarkDamaged(video_path, report) => {
+                let index = self
+                    .video_list_factory
+                    .iter()
+                    .flatten()
+                    .find(|video_model| video_model.video.path == video_path)
+                    .map(|video_model| video_model.index.current_index());
+                if let Some(index) = index {
+                    self.video_list_factory
+                        .send(index, VideoInput::SetDamaged(report));
+                }
+            }
             VideoListInput::OpenExtractDialog => {
                 let guard = self.video_list_factory.guard();
                 let selected_videos: Vec<&VideoModel> = guard
@@ -256,8 +321,13 @@ impl AsyncComponent for VideoListModel {
                         }
                     }
                     2.. => {
+                        let tracks = selected_videos
+                            .iter()
+                            .map(|video_model| video_model.video.metadata.video_tracks().count())
+                            .max()
+                            .unwrap_or(0);
                         self.extract_dialog
-                            .emit(ExtractDialogInput::Show(ExtractDialogType::Multi));
+                            .emit(ExtractDialogInput::Show(ExtractDialogType::Multi(tracks)));
                     }
                     _ => {
                         sender
@@ -270,9 +340,24 @@ impl AsyncComponent for VideoListModel {
                 }
             }
             VideoListInput::ExtractDialogResponse(response) => match response {
-                ExtractDialogResponse::ExtractToImage(layout_type, show_timestamp, dst_path) => {
-                    self.on_extract_dialog_to_image(layout_type, show_timestamp, dst_path, &sender)
-                        .await;
+                ExtractDialogResponse::ExtractToImage(
+                    layout_type,
+                    show_timestamp,
+                    dst_path,
+                    options,
+                    tracks,
+                    preview,
+                ) => {
+                    self.on_extract_dialog_to_image(
+                        layout_type,
+                        show_timestamp,
+                        dst_path,
+                        options,
+                        tracks,
+                        preview,
+                        &sender,
+                    )
+                    .await;
                 }
                 ExtractDialogResponse::ExtractFrames(
                     time_start,
@@ -280,6 +365,9 @@ impl AsyncComponent for VideoListModel {
                     frame_rate,
                     show_timestamp,
                     dst_path,
+                    options,
+                    tracks,
+                    preview,
                 ) => {
                     self.on_extract_dialog_frames(
                         time_start,
@@ -287,6 +375,56 @@ impl AsyncComponent for VideoListModel {
                         frame_rate,
                         show_timestamp,
                         dst_path,
+                        options,
+                        tracks,
+                        preview,
+                        &sender,
+                    )
+                    .await;
+                }
+                ExtractDialogResponse::Capture(dst_path, options) => {
+                    self.on_extract_dialog_capture(dst_path, options, &sender)
+                        .await;
+                }
+                ExtractDialogResponse::ExportClip(time_start, time_end, dst_path, options) => {
+                    self.on_extract_dialog_clip(time_start, time_end, dst_path, options, &sender)
+                        .await;
+                }
+                ExtractDialogResponse::StackFrames(
+                    time_start,
+                    time_end,
+                    frame_rate,
+                    dst_path,
+                    options,
+                ) => {
+                    self.on_extract_dialog_stack(
+                        time_start, time_end, frame_rate, dst_path, options, &sender,
+                    )
+                    .await;
+                }
+                ExtractDialogResponse::ExtractHeatmaps(interval, dst_path, options) => {
+                    self.on_extract_dialog_heatmaps(interval, dst_path, options, &sender)
+                        .await;
+                }
+                ExtractDialogResponse::SlitScan(dst_path, options) => {
+                    self.on_extract_dialog_slit_scan(dst_path, options, &sender)
+                        .await;
+                }
+                ExtractDialogResponse::Timelapse(dst_path, options) => {
+                    self.on_extract_dialog_timelapse(dst_path, options, &sender)
+                        .await;
+                }
+                ExtractDialogResponse::ExtractSprites(
+                    show_timestamp,
+                    dst_path,
+                    options,
+                    sprite,
+                ) => {
+                    self.on_extract_dialog_sprites(
+                        show_timestamp,
+                        dst_path,
+                        options,
+                        sprite,
                         &sender,
                     )
                     .await;
@@ -381,10 +519,32 @@ impl AsyncComponent for VideoListModel {
 
 impl VideoListModel {
     async fn on_search(&mut self, path: PathBuf, sender: &AsyncComponentSender<VideoListModel>) {
+        let (thumbnail_position, deinterlace, input) = settings::get_settings()
+            .map(|settings_toml| {
+                (
+                    settings_toml.thumbnail_position as f64 / 100.0,
+                    settings_toml.deinterlace,
+                    settings_toml.input,
+                )
+            })
+            .unwrap_or((
+                service::THUMBNAIL_SAMPLE_POSITION,
+                Deinterlace::Off,
+                InputOptions::default(),
+            ));
+
         sender.oneshot_command(async move {
-            match service::search_videos(path).await {
+            match service::search_videos(path, input).await {
                 Ok(videos) => {
-                    let videos = videos.iter().map(models::Video::from).collect();
+                    let videos = videos
+                        .iter()
+                        .map(|video| models::Video {
+                            thumbnail_position,
+                            deinterlace,
+                            input: input.for_path(&video.path),
+                            ..models::Video::from(video)
+                        })
+                        .collect();
                     VideoListCommandOutput::SearchCompleted(Ok(videos))
                 }
                 Err(err) => VideoListCommandOutput::SearchCompleted(Err(err)),
@@ -409,11 +569,15 @@ impl VideoListModel {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn on_extract_dialog_to_image(
         &mut self,
         layout_type: models::LayoutType,
         show_timestamp: bool,
         dst_path: PathBuf,
+        options: DecodeOptions,
+        tracks: models::TrackSelection,
+        preview: Option<PreviewOptions>,
         sender: &AsyncComponentSender<VideoListModel>,
     ) {
         let videos_list: Vec<String> = self
@@ -431,6 +595,9 @@ impl VideoListModel {
                     layout_type,
                     show_timestamp,
                     dst_path,
+                    options,
+                    tracks,
+                    preview,
                 ))
                 .unwrap_or_default();
         } else {
@@ -443,6 +610,7 @@ impl VideoListModel {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn on_extract_dialog_frames(
         &mut self,
         time_start: u32,
@@ -450,25 +618,165 @@ impl VideoListModel {
         frame_rate: u32,
         show_timestamp: bool,
         dst_path: PathBuf,
+        options: DecodeOptions,
+        tracks: models::TrackSelection,
+        preview: Option<PreviewOptions>,
         sender: &AsyncComponentSender<VideoListModel>,
     ) {
-        let video_path = self
+        if let Some(video_path) = self.selected_video_path(sender) {
+            sender
+                .output(VideoListOutput::ExtractFramesFromVideo(
+                    video_path,
+                    time_start,
+                    time_end,
+                    frame_rate,
+                    show_timestamp,
+                    dst_path,
+                    options,
+                    tracks,
+                    preview,
+                ))
+                .unwrap_or_default();
+        }
+    }
+
+    async fn on_extract_dialog_capture(
+        &mut self,
+        dst_path: PathBuf,
+        options: CaptureOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        if let Some(video_path) = self.selected_video_path(sender) {
+            sender
+                .output(VideoListOutput::StartCapture(video_path, dst_path, options))
+                .unwrap_or_default();
+        }
+    }
+
+    async fn on_extract_dialog_clip(
+        &mut self,
+        time_start: u32,
+        time_end: u32,
+        dst_path: PathBuf,
+        options: ClipOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        if let Some(video_path) = self.selected_video_path(sender) {
+            sender
+                .output(VideoListOutput::ExportClip(
+                    video_path, time_start, time_end, dst_path, options,
+                ))
+                .unwrap_or_default();
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn on_extract_dialog_stack(
+        &mut self,
+        time_start: u32,
+        time_end: u32,
+        frame_rate: u32,
+        dst_path: PathBuf,
+        options: StackOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        if let Some(video_path) = self.selected_video_path(sender) {
+            sender
+                .output(VideoListOutput::StackFrames(
+                    video_path, time_start, time_end, frame_rate, dst_path, options,
+                ))
+                .unwrap_or_default();
+        }
+    }
+
+    async fn on_extract_dialog_heatmaps(
+        &mut self,
+        interval: u32,
+        dst_path: PathBuf,
+        options: DecodeOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        let videos_list: Vec<String> = self
             .video_list_factory
             .guard()
             .iter()
             .filter(|&video_model| video_model.unwrap().video.is_selected)
             .map(|video_model| video_model.unwrap().video.path.clone())
-            .last();
+            .collect();
 
-        if let Some(video_path) = video_path {
+        if !videos_list.is_empty() {
             sender
-                .output(VideoListOutput::ExtractFramesFromVideo(
-                    video_path,
-                    time_start,
-                    time_end,
-                    frame_rate,
+                .output(VideoListOutput::ExtractHeatmaps(
+                    videos_list,
+                    interval,
+                    dst_path,
+                    options,
+                ))
+                .unwrap_or_default();
+        } else {
+            sender
+                .output(VideoListOutput::Notify(
+                    fl!("select-one-video").to_string(),
+                    5,
+                ))
+                .unwrap_or_default();
+        }
+    }
+
+    async fn on_extract_dialog_timelapse(
+        &mut self,
+        dst_path: PathBuf,
+        options: TimelapseOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        if let Some(video_path) = self.selected_video_path(sender) {
+            sender
+                .output(VideoListOutput::StartTimelapse(
+                    video_path, dst_path, options,
+                ))
+                .unwrap_or_default();
+        }
+    }
+
+    async fn on_extract_dialog_slit_scan(
+        &mut self,
+        dst_path: PathBuf,
+        options: SlitScanOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        if let Some(video_path) = self.selected_video_path(sender) {
+            sender
+                .output(VideoListOutput::ExtractSlitScan(
+                    video_path, dst_path, options,
+                ))
+                .unwrap_or_default();
+        }
+    }
+
+    async fn on_extract_dialog_sprites(
+        &mut self,
+        show_timestamp: bool,
+        dst_path: PathBuf,
+        options: DecodeOptions,
+        sprite: SpriteOptions,
+        sender: &AsyncComponentSender<VideoListModel>,
+    ) {
+        let videos_list: Vec<String> = self
+            .video_list_factory
+            .guard()
+            .iter()
+            .filter(|&video_model| video_model.unwrap().video.is_selected)
+            .map(|video_model| video_model.unwrap().video.path.clone())
+            .collect();
+
+        if !videos_list.is_empty() {
+            sender
+                .output(VideoListOutput::ExtractSprites(
+                    videos_list,
                     show_timestamp,
                     dst_path,
+                    options,
+                    sprite,
                 ))
                 .unwrap_or_default();
         } else {
@@ -481,6 +789,28 @@ impl VideoListModel {
         }
     }
 
+    /// Path of the selected video for the actions that take a single one,
+    /// asking for a selection when there is none.
+    fn selected_video_path(&self, sender: &AsyncComponentSender<VideoListModel>) -> Option<String> {
+        let video_path = self
+            .video_list_factory
+            .guard()
+            .iter()
+            .filter(|&video_model| video_model.unwrap().video.is_selected)
+            .map(|video_model| video_model.unwrap().video.path.clone())
+            .last();
+
+        if video_path.is_none() {
+            sender
+                .output(VideoListOutput::Notify(
+                    fl!("select-one-video").to_string(),
+                    5,
+                ))
+                .unwrap_or_default();
+        }
+        video_path
+    }
+
     async fn on_select_all_videos(&mut self, is_selected: bool) {
         self.video_list_factory
             .guard()
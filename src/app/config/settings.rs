@@ -8,7 +8,8 @@ use std::io::Write;
 use toml;
 
 use super::localization;
-use crate::app::models::{ColorScheme, Preference};
+use crate::app::models::{self, ColorScheme, Preference};
+use core_vimpeg::video::{service::PreviewOptions, Deinterlace, InputOptions};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SettingsToml {
@@ -16,6 +17,27 @@ pub struct SettingsToml {
     pub theme: ColorScheme,
     #[allow(dead_code)]
     pub language: String,
+    #[serde(default = "default_thumbnail_position")]
+    pub thumbnail_position: u32,
+    #[serde(default)]
+    pub deinterlace: Deinterlace,
+    #[serde(default = "default_decoder_threads")]
+    pub decoder_threads: usize,
+    /// Videos extracted at the same time, 0 is automatic.
+    #[serde(default)]
+    pub parallel_videos: usize,
+    #[serde(default)]
+    pub input: InputOptions,
+    #[serde(default)]
+    pub preview: PreviewOptions,
+}
+
+fn default_thumbnail_position() -> u32 {
+    models::preference::DEFAULT_THUMBNAIL_POSITION
+}
+
+fn default_decoder_threads() -> usize {
+    models::preference::DEFAULT_DECODER_THREADS
 }
 
 pub(crate) fn init() -> anyhow::Result<()> {
@@ -70,6 +92,12 @@ pub(crate) async fn save_preferences(preference: &Preference) -> anyhow::Result<
     let settings_toml = SettingsToml {
         theme: preference.color_scheme,
         language: preference.language.to_string(),
+        thumbnail_position: preference.thumbnail_position,
+        deinterlace: preference.deinterlace,
+        decoder_threads: preference.decoder_threads,
+        parallel_videos: preference.parallel_videos,
+        input: preference.input,
+        preview: preference.preview,
     };
     set_settings(&settings_toml).context("Failed to save preferences.")?;
 
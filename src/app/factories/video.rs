@@ -5,8 +5,8 @@ use relm4::{
         gdk_pixbuf::{Colorspace, Pixbuf},
         glib, pango,
         prelude::{
-            BoxExt, CheckButtonExt, GestureExt, GestureSingleExt, OrientableExt, PaintableExt,
-            PopoverExt, WidgetExt,
+            BoxExt, ButtonExt, CheckButtonExt, GestureExt, GestureSingleExt, OrientableExt,
+            PaintableExt, PopoverExt, WidgetExt,
         },
     },
     loading_widgets::LoadingWidgets,
@@ -15,7 +15,7 @@ use relm4::{
 
 use crate::app::models;
 use crate::fl;
-use core_vimpeg::video::service;
+use core_vimpeg::video::service::{self, DecodeReport};
 
 pub struct VideoModel {
     pub video: models::Video,
@@ -36,11 +36,13 @@ pub enum VideoInput {
     SetVisible(bool),
     ZoomIn(i32),
     ZoomOut(i32),
+    SetDamaged(DecodeReport),
 }
 
 #[derive(Debug)]
 pub enum VideoOutput {
     Selected(bool),
+    ShowDetails(usize),
 }
 
 #[relm4::factory(pub async)]
@@ -80,6 +82,18 @@ impl AsyncFactoryComponent for VideoModel {
                     }
                 },
 
+                add_overlay = &gtk::Label {
+                    set_label: fl!("damaged"),
+                    set_halign: gtk::Align::End,
+                    set_valign: gtk::Align::Start,
+                    set_margin_all: 4,
+                    set_css_classes: &["error", "caption-heading"],
+                    #[watch]
+                    set_visible: self.video.decode_report.is_damaged(),
+                    #[watch]
+                    set_tooltip_text: Some(&damage_description(&self.video.decode_report)),
+                },
+
                 #[name(checkbox)]
                 add_overlay = &gtk::CheckButton {
                     set_halign: gtk::Align::Start,
@@ -203,6 +217,18 @@ impl AsyncFactoryComponent for VideoModel {
                             },
                         },
                     },
+
+                    gtk::Button {
+                        set_label: fl!("details"),
+                        set_margin_top: 4,
+                        add_css_class: "flat",
+                        connect_clicked[sender, popover, index = self.index.clone()] => move |_| {
+                            popover.popdown();
+                            sender
+                                .output(VideoOutput::ShowDetails(index.current_index()))
+                                .unwrap_or_default();
+                        },
+                    },
                 }
             }
         },
@@ -236,7 +262,14 @@ impl AsyncFactoryComponent for VideoModel {
         _sender: AsyncFactorySender<Self>,
     ) -> Self {
         let filename = video.path.as_str();
-        let paintable = match service::get_video_thumbnail(filename).await {
+        let paintable = match service::get_video_thumbnail(
+            filename,
+            video.thumbnail_position,
+            video.deinterlace,
+            video.input,
+        )
+        .await
+        {
             Ok(thumb) => {
                 if let Some(data) = thumb.data.as_ref() {
                     let bytes = glib::Bytes::from(data);
@@ -298,6 +331,26 @@ impl AsyncFactoryComponent for VideoModel {
             VideoInput::ZoomOut(size) => {
                 self.video.thumbnail_size = size;
             }
+            VideoInput::SetDamaged(report) => {
+                self.video.decode_report = report;
+            }
         }
     }
 }
+
+fn damage_description(report: &DecodeReport) -> String {
+    let description = format!(
+        "{}: {}\n{}: {}\n{}: {}",
+        fl!("corrupt-packets"),
+        report.corrupt_packets,
+        fl!("corrupt-frames"),
+        report.corrupt_frames,
+        fl!("discontinuities"),
+        report.discontinuities
+    );
+    if report.failed {
+        format!("{}\n{}", fl!("decoding-failed"), description)
+    } else {
+        description
+    }
+}
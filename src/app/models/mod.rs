@@ -1,6 +1,6 @@
 pub mod image;
 mod layout;
-mod preference;
+pub mod preference;
 pub mod video;
 
 pub use image::Image;
@@ -9,4 +9,4 @@ pub use layout::LayoutType;
 pub use preference::ColorScheme;
 pub use preference::Language;
 pub use preference::Preference;
-pub use video::{Video, VideoFilter};
+pub use video::{TrackSelection, Video, VideoFilter};
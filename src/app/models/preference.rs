@@ -3,6 +3,19 @@ use core::fmt;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+use core_vimpeg::video::{service::PreviewOptions, Deinterlace, InputOptions};
+
+/// Percentage of the video where the thumbnail sampling begins.
+pub const DEFAULT_THUMBNAIL_POSITION: u32 = 10;
+/// Threads of each video decoder.
+pub const DEFAULT_DECODER_THREADS: usize = 2;
+
+/// Videos extracted at the same time when the preference is 0 (automatic),
+/// so the decoders leave cores free for the interface.
+pub fn automatic_parallel_videos() -> usize {
+    (num_cpus::get() / 2).max(1)
+}
+
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
 pub enum ColorScheme {
     Dark,
@@ -66,6 +79,12 @@ impl FromStr for Language {
 pub struct Preference {
     pub color_scheme: ColorScheme,
     pub language: Language,
+    pub thumbnail_position: u32,
+    pub deinterlace: Deinterlace,
+    pub decoder_threads: usize,
+    pub parallel_videos: usize,
+    pub input: InputOptions,
+    pub preview: PreviewOptions,
 }
 
 impl Default for Preference {
@@ -73,15 +92,37 @@ impl Default for Preference {
         Self {
             color_scheme: ColorScheme::Default,
             language: Language::English,
+            thumbnail_position: DEFAULT_THUMBNAIL_POSITION,
+            deinterlace: Deinterlace::Off,
+            decoder_threads: DEFAULT_DECODER_THREADS,
+            parallel_videos: 0,
+            input: InputOptions::default(),
+            preview: PreviewOptions::default(),
         }
     }
 }
 
 impl Preference {
-    pub fn new(color_scheme: ColorScheme, language: Language) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        color_scheme: ColorScheme,
+        language: Language,
+        thumbnail_position: u32,
+        deinterlace: Deinterlace,
+        decoder_threads: usize,
+        parallel_videos: usize,
+        input: InputOptions,
+        preview: PreviewOptions,
+    ) -> Self {
         Self {
             color_scheme,
             language,
+            thumbnail_position,
+            deinterlace,
+            decoder_threads,
+            parallel_videos,
+            input,
+            preview,
         }
     }
 }
@@ -15,6 +15,11 @@ pub struct Video {
     pub height: u32,
     pub is_selected: bool,
     pub thumbnail_size: i32,
+    pub thumbnail_position: f64,
+    pub deinterlace: core_vimpeg::video::Deinterlace,
+    pub input: core_vimpeg::video::InputOptions,
+    pub metadata: core_vimpeg::video::MediaInfo,
+    pub decode_report: core_vimpeg::video::service::DecodeReport,
 }
 
 impl From<&core_vimpeg::video::Video> for Video {
@@ -34,10 +39,25 @@ impl From<&core_vimpeg::video::Video> for Video {
             height: value.height,
             is_selected: false,
             thumbnail_size: THUMBNAIL_SIZE,
+            thumbnail_position: core_vimpeg::video::service::THUMBNAIL_SAMPLE_POSITION,
+            deinterlace: core_vimpeg::video::Deinterlace::Off,
+            input: Default::default(),
+            metadata: value.metadata.clone(),
+            decode_report: Default::default(),
         }
     }
 }
 
+/// Video tracks picked for an extraction.
+#[derive(Debug, Clone, Default)]
+pub struct TrackSelection {
+    /// Tracks counted from 0, the best video stream is used when empty.
+    pub tracks: Vec<usize>,
+    /// Puts the tracks side by side in the tiles of one contact sheet
+    /// instead of one contact sheet per track.
+    pub side_by_side: bool,
+}
+
 #[derive(Debug)]
 pub struct VideoFilter {
     pub search_entry: Option<String>,